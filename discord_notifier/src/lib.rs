@@ -6,6 +6,7 @@ use weil_rs::http::{HttpClient, HttpMethod};
 use weil_rs::config::Secrets;
 use std::collections::HashMap;
 use serde_json::json;
+use incident_common::{IncidentStatus, Severity};
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Default)]
 pub struct DiscordConfig {
@@ -87,13 +88,8 @@ async fn send_incident_alert(
     let config = self.secrets.config();
     
     // Map severity to Discord embed colors (decimal format)
-    let color = match severity.as_str() {
-        "P0" => 16711680,  // Red (#FF0000)
-        "P1" => 16753920,  // Orange (#FFA500)
-        "P2" => 16776960,  // Yellow (#FFFF00)
-        "P3" => 65280,     // Green (#00FF00)
-        _ => 8421504       // Gray (#808080)
-    };
+    let severity: Severity = severity.parse()?;
+    let color = severity.color();
     
     // Get current timestamp
     //let timestamp = chrono::Utc::now().to_rfc3339();
@@ -155,21 +151,10 @@ async fn send_status_update(
 ) -> Result<String, String> {
     let config = self.secrets.config();
     
-    // Map status to colors
-    let color = match status.as_str() {
-        "investigating" => 16776960,  // Yellow
-        "resolved" => 65280,          // Green
-        "closed" => 8421504,          // Gray
-        _ => 3447003                  // Blue
-    };
-    
-    // Choose emoji based on status
-    let emoji = match status.as_str() {
-        "investigating" => "🔍",
-        "resolved" => "✅",
-        "closed" => "🔒",
-        _ => "📝"
-    };
+    // Map status to colors and emoji
+    let status: IncidentStatus = status.parse()?;
+    let color = status.color();
+    let emoji = status.emoji();
     
     //let timestamp = chrono::Utc::now().to_rfc3339();
     
@@ -182,7 +167,7 @@ async fn send_status_update(
             "fields": [
                 {
                     "name": "New Status",
-                    "value": status.to_uppercase(),
+                    "value": status.as_str().to_uppercase(),
                     "inline": true
                 },
                 {
@@ -0,0 +1,175 @@
+//! Types shared by every SaveTheProd MCP contract, so that severities and
+//! statuses are parsed once and unknown values are rejected everywhere
+//! instead of each contract silently falling back to its own default.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    P0,
+    P1,
+    P2,
+    P3,
+}
+
+impl Severity {
+    pub const ALL: [Severity; 4] = [Severity::P0, Severity::P1, Severity::P2, Severity::P3];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::P0 => "P0",
+            Severity::P1 => "P1",
+            Severity::P2 => "P2",
+            Severity::P3 => "P3",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::P0 => "critical",
+            Severity::P1 => "high",
+            Severity::P2 => "medium",
+            Severity::P3 => "low",
+        }
+    }
+
+    // RGB color used by chat integrations
+    pub fn color(&self) -> u32 {
+        match self {
+            Severity::P0 => 0xFF0000, // Red
+            Severity::P1 => 0xFFA500, // Orange
+            Severity::P2 => 0xFFFF00, // Yellow
+            Severity::P3 => 0x00FF00, // Green
+        }
+    }
+
+    pub fn hex_color(&self) -> String {
+        format!("#{:06X}", self.color())
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    // Accepts P0-P3, SEV0-SEV3 and the common names used by alerting tools
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().replace(['-', '_', ' '], "").as_str() {
+            "P0" | "SEV0" | "CRITICAL" => Ok(Severity::P0),
+            "P1" | "SEV1" | "HIGH" | "MAJOR" => Ok(Severity::P1),
+            "P2" | "SEV2" | "MEDIUM" | "MODERATE" => Ok(Severity::P2),
+            "P3" | "SEV3" | "LOW" | "MINOR" => Ok(Severity::P3),
+            _ => Err(format!(
+                "Unknown severity '{}' (expected P0-P3 or critical, high, medium, low)",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IncidentStatus {
+    Open,
+    Acknowledged,
+    Investigating,
+    Mitigated,
+    Resolved,
+    Closed,
+}
+
+impl IncidentStatus {
+    pub const ALL: [IncidentStatus; 6] = [
+        IncidentStatus::Open,
+        IncidentStatus::Acknowledged,
+        IncidentStatus::Investigating,
+        IncidentStatus::Mitigated,
+        IncidentStatus::Resolved,
+        IncidentStatus::Closed,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IncidentStatus::Open => "open",
+            IncidentStatus::Acknowledged => "acknowledged",
+            IncidentStatus::Investigating => "investigating",
+            IncidentStatus::Mitigated => "mitigated",
+            IncidentStatus::Resolved => "resolved",
+            IncidentStatus::Closed => "closed",
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !matches!(self, IncidentStatus::Resolved | IncidentStatus::Closed)
+    }
+
+    // Statuses reachable from this one. Work moves forward through the
+    // lifecycle (steps may be skipped), a mitigation can fall back to
+    // investigating, and only resolved or closed incidents can be reopened.
+    pub fn next(&self) -> &'static [IncidentStatus] {
+        use IncidentStatus::*;
+        match self {
+            Open => &[Acknowledged, Investigating, Mitigated, Resolved],
+            Acknowledged => &[Investigating, Mitigated, Resolved],
+            Investigating => &[Mitigated, Resolved],
+            Mitigated => &[Investigating, Resolved],
+            Resolved => &[Closed, Open],
+            Closed => &[Open],
+        }
+    }
+
+    pub fn can_transition_to(&self, to: IncidentStatus) -> bool {
+        self.next().contains(&to)
+    }
+
+    // RGB color used by chat integrations
+    pub fn color(&self) -> u32 {
+        match self {
+            IncidentStatus::Open => 0xFF0000,          // Red
+            IncidentStatus::Acknowledged => 0xFFA500,  // Orange
+            IncidentStatus::Investigating => 0xFFFF00, // Yellow
+            IncidentStatus::Mitigated => 0x3498DB,     // Blue
+            IncidentStatus::Resolved => 0x00FF00,      // Green
+            IncidentStatus::Closed => 0x808080,        // Gray
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            IncidentStatus::Open => "🚨",
+            IncidentStatus::Acknowledged => "👀",
+            IncidentStatus::Investigating => "🔍",
+            IncidentStatus::Mitigated => "🩹",
+            IncidentStatus::Resolved => "✅",
+            IncidentStatus::Closed => "🔒",
+        }
+    }
+}
+
+impl fmt::Display for IncidentStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IncidentStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase();
+        IncidentStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == normalized)
+            .ok_or_else(|| {
+                let expected: Vec<&str> = IncidentStatus::ALL.iter().map(|s| s.as_str()).collect();
+                format!("Unknown status '{}' (expected one of: {})", s, expected.join(", "))
+            })
+    }
+}
@@ -12,9 +12,45 @@ trait IncidentTracker {
         Self: Sized;
     async fn create_incident(&mut self, description: String, severity: String, service: String, reporter: String, timestamp: String) -> Result<String, String>;
     async fn log_action(&mut self, incident_id: String, action_type: String, details: String, timestamp: String) -> Result<String, String>;
-    async fn update_status(&mut self, incident_id: String, status: String, notes: String) -> Result<String, String>;
+    async fn update_status(&mut self, incident_id: String, status: String, notes: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn change_severity(&mut self, incident_id: String, severity: String, reason: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn acknowledge_incident(&mut self, incident_id: String, responder: String, timestamp: String) -> Result<String, String>;
+    async fn assign_incident(&mut self, incident_id: String, assignee: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn reassign_incident(&mut self, incident_id: String, assignee: String, actor: String, reason: String, timestamp: String) -> Result<String, String>;
+    async fn link_incidents(&mut self, incident_id: String, target_id: String, relation: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn unlink_incident(&mut self, incident_id: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn merge_incidents(&mut self, source_id: String, survivor_id: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn import_incidents(&mut self, batch: String, format: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn grant_role(&mut self, identity: String, role: String, timestamp: String) -> Result<String, String>;
+    async fn revoke_role(&mut self, identity: String, timestamp: String) -> Result<String, String>;
+    async fn get_role(&self, identity: String) -> Result<String, String>;
+    async fn get_access_denials(&self, limit: u32) -> Result<String, String>;
+    async fn add_comment(&mut self, incident_id: String, author: String, text: String, visibility: String, timestamp: String) -> Result<String, String>;
+    async fn edit_comment(&mut self, incident_id: String, comment_id: String, text: String, editor: String, timestamp: String) -> Result<String, String>;
+    async fn list_comments(&self, incident_id: String, visibility: String) -> Result<String, String>;
+    async fn attach_evidence(&mut self, incident_id: String, name: String, content_type: String, content: String, encoding: String, uploader: String, timestamp: String) -> Result<String, String>;
+    async fn list_evidence(&self, incident_id: String) -> Result<String, String>;
+    async fn get_evidence(&self, hash: String) -> Result<ByteStream, String>;
+    async fn add_action_item(&mut self, incident_id: String, title: String, owner: String, due_date: String, priority: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn update_action_item(&mut self, incident_id: String, item_id: String, owner: String, due_date: String, priority: String, status: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn list_action_items(&self, incident_id: String, status: String) -> Result<String, String>;
+    async fn get_overdue_action_items(&self, as_of: String) -> Result<String, String>;
+    async fn update_impact(&mut self, incident_id: String, started_at: String, ended_at: String, regions: String, affected_users: String, components: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn add_labels(&mut self, incident_id: String, labels: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn remove_labels(&mut self, incident_id: String, keys: String, actor: String, timestamp: String) -> Result<String, String>;
     async fn get_incident_timeline(&self, incident_id: String) -> Result<String, String>;
+    async fn verify_incident_integrity(&self, incident_id: String) -> Result<String, String>;
+    async fn get_merkle_root(&self, incident_id: String) -> Result<String, String>;
+    async fn generate_postmortem(&self, incident_id: String, format: String) -> Result<String, String>;
+    async fn search_incidents(&self, query: String, limit: u32) -> Result<String, String>;
     async fn get_active_incidents(&self) -> Result<String, String>;
+    async fn query_incidents(&self, severity: String, service: String, status: String, reporter: String, created_from: String, created_to: String, labels: String, cursor: String, limit: u32) -> Result<String, String>;
+    async fn export_incident_data(&self, dataset: String, format: String, from: String, to: String, cursor: String, limit: u32) -> Result<String, String>;
+    async fn get_export_schemas(&self) -> Result<String, String>;
+    async fn get_response_metrics(&self, from: String, to: String) -> Result<String, String>;
+    async fn plot_incidents_per_day(&self, from: String, to: String) -> Result<Plottable, String>;
+    async fn plot_open_incidents(&self, from: String, to: String) -> Result<Plottable, String>;
+    async fn plot_mttr_per_week(&self, from: String, to: String) -> Result<Plottable, String>;
     fn tools(&self) -> String;
     fn prompts(&self) -> String;
 }
@@ -46,7 +82,132 @@ impl IncidentTracker for IncidentTrackerContractState {
     }
 
     #[mutate]
-    async fn update_status(&mut self, incident_id: String, status: String, notes: String) -> Result<String, String> {
+    async fn update_status(&mut self, incident_id: String, status: String, notes: String, actor: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn change_severity(&mut self, incident_id: String, severity: String, reason: String, actor: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn acknowledge_incident(&mut self, incident_id: String, responder: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn assign_incident(&mut self, incident_id: String, assignee: String, actor: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn reassign_incident(&mut self, incident_id: String, assignee: String, actor: String, reason: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn link_incidents(&mut self, incident_id: String, target_id: String, relation: String, actor: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn unlink_incident(&mut self, incident_id: String, actor: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn merge_incidents(&mut self, source_id: String, survivor_id: String, actor: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn import_incidents(&mut self, batch: String, format: String, actor: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn grant_role(&mut self, identity: String, role: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn revoke_role(&mut self, identity: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn get_role(&self, identity: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn get_access_denials(&self, limit: u32) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn add_comment(&mut self, incident_id: String, author: String, text: String, visibility: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn edit_comment(&mut self, incident_id: String, comment_id: String, text: String, editor: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn list_comments(&self, incident_id: String, visibility: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn attach_evidence(&mut self, incident_id: String, name: String, content_type: String, content: String, encoding: String, uploader: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn list_evidence(&self, incident_id: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn get_evidence(&self, hash: String) -> Result<ByteStream, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn add_action_item(&mut self, incident_id: String, title: String, owner: String, due_date: String, priority: String, actor: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn update_action_item(&mut self, incident_id: String, item_id: String, owner: String, due_date: String, priority: String, status: String, actor: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn list_action_items(&self, incident_id: String, status: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn get_overdue_action_items(&self, as_of: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn update_impact(&mut self, incident_id: String, started_at: String, ended_at: String, regions: String, affected_users: String, components: String, actor: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn add_labels(&mut self, incident_id: String, labels: String, actor: String, timestamp: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[mutate]
+    async fn remove_labels(&mut self, incident_id: String, keys: String, actor: String, timestamp: String) -> Result<String, String> {
         unimplemented!();
     }
 
@@ -55,11 +216,66 @@ impl IncidentTracker for IncidentTrackerContractState {
         unimplemented!();
     }
 
+    #[query]
+    async fn verify_incident_integrity(&self, incident_id: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn get_merkle_root(&self, incident_id: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn generate_postmortem(&self, incident_id: String, format: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn search_incidents(&self, query: String, limit: u32) -> Result<String, String> {
+        unimplemented!();
+    }
+
     #[query]
     async fn get_active_incidents(&self) -> Result<String, String> {
         unimplemented!();
     }
 
+    #[query]
+    async fn query_incidents(&self, severity: String, service: String, status: String, reporter: String, created_from: String, created_to: String, labels: String, cursor: String, limit: u32) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn export_incident_data(&self, dataset: String, format: String, from: String, to: String, cursor: String, limit: u32) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn get_export_schemas(&self) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn get_response_metrics(&self, from: String, to: String) -> Result<String, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn plot_incidents_per_day(&self, from: String, to: String) -> Result<Plottable, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn plot_open_incidents(&self, from: String, to: String) -> Result<Plottable, String> {
+        unimplemented!();
+    }
+
+    #[query]
+    async fn plot_mttr_per_week(&self, from: String, to: String) -> Result<Plottable, String> {
+        unimplemented!();
+    }
+
 
     #[query]
     fn tools(&self) -> String {
@@ -68,7 +284,7 @@ impl IncidentTracker for IncidentTrackerContractState {
     "type": "function",
     "function": {
       "name": "create_incident",
-      "description": "Creates new incident record on-chain\n",
+      "description": "Creates new incident record on-chain and suggests similar past incidents with their resolution notes\n",
       "parameters": {
         "type": "object",
         "properties": {
@@ -90,7 +306,7 @@ impl IncidentTracker for IncidentTrackerContractState {
           },
           "timestamp": {
             "type": "string",
-            "description": "timestamp\n"
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
           }
         },
         "required": [
@@ -117,15 +333,15 @@ impl IncidentTracker for IncidentTrackerContractState {
           },
           "action_type": {
             "type": "string",
-            "description": "action type (notified, escalated, resolved)\n"
+            "description": "action type (notified, escalated, paged, war_room_created, status_page_updated, remediation_applied, comment)\n"
           },
           "details": {
             "type": "string",
-            "description": "action details JSON\n"
+            "description": "action details JSON, with fields per action type: notified {channel, recipients?, message?}, escalated {to, reason}, paged {responder, via?}, war_room_created {url}, status_page_updated {status, message}, remediation_applied {action, outcome?}, comment {text}\n"
           },
           "timestamp": {
             "type": "string",
-            "description": "timestamp\n"
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
           }
         },
         "required": [
@@ -141,7 +357,7 @@ impl IncidentTracker for IncidentTrackerContractState {
     "type": "function",
     "function": {
       "name": "update_status",
-      "description": "Updates incident status\n",
+      "description": "Moves incident through its lifecycle, rejecting illegal transitions; linked incidents follow. Closing is blocked while P0 action items are unassigned.\n",
       "parameters": {
         "type": "object",
         "properties": {
@@ -151,17 +367,27 @@ impl IncidentTracker for IncidentTrackerContractState {
           },
           "status": {
             "type": "string",
-            "description": "new status (investigating, resolved, closed)\n"
+            "description": "new status (open, acknowledged, investigating, mitigated, resolved, closed)\n"
           },
           "notes": {
             "type": "string",
-            "description": "resolution notes\n"
+            "description": "notes on the transition; when resolving, they become the resolution notes\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is making the change\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
           }
         },
         "required": [
           "incident_id",
           "status",
-          "notes"
+          "notes",
+          "actor",
+          "timestamp"
         ]
       }
     }
@@ -169,18 +395,67 @@ impl IncidentTracker for IncidentTrackerContractState {
   {
     "type": "function",
     "function": {
-      "name": "get_incident_timeline",
-      "description": "Retrieves complete incident timeline\n",
+      "name": "change_severity",
+      "description": "Escalates or de-escalates incident severity, flagging new P0s for re-paging\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "severity": {
+            "type": "string",
+            "description": "new severity P0-P3\n"
+          },
+          "reason": {
+            "type": "string",
+            "description": "why the severity changed\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is making the change\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "severity",
+          "reason",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "acknowledge_incident",
+      "description": "Acknowledges an incident, taking ownership if it is unassigned\n",
       "parameters": {
         "type": "object",
         "properties": {
           "incident_id": {
             "type": "string",
             "description": "incident ID\n"
+          },
+          "responder": {
+            "type": "string",
+            "description": "responder acknowledging the incident\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
           }
         },
         "required": [
-          "incident_id"
+          "incident_id",
+          "responder",
+          "timestamp"
         ]
       }
     }
@@ -188,12 +463,1085 @@ impl IncidentTracker for IncidentTrackerContractState {
   {
     "type": "function",
     "function": {
-      "name": "get_active_incidents",
-      "description": "Gets all active incidents\n",
+      "name": "assign_incident",
+      "description": "Assigns an unassigned incident to a responder\n",
       "parameters": {
         "type": "object",
-        "properties": {},
-        "required": []
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "assignee": {
+            "type": "string",
+            "description": "responder who will own the incident\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is making the assignment\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "assignee",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "reassign_incident",
+      "description": "Hands an assigned incident over to another responder\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "assignee": {
+            "type": "string",
+            "description": "responder who will own the incident\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is making the assignment\n"
+          },
+          "reason": {
+            "type": "string",
+            "description": "reason for the handover\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "assignee",
+          "actor",
+          "reason",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "link_incidents",
+      "description": "Links an incident under another as a child or as a duplicate\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID to link\n"
+          },
+          "target_id": {
+            "type": "string",
+            "description": "parent incident ID\n"
+          },
+          "relation": {
+            "type": "string",
+            "description": "relation to the parent (child, duplicate)\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is creating the link\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "target_id",
+          "relation",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "unlink_incident",
+      "description": "Removes an incident's link to its parent\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is removing the link\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "merge_incidents",
+      "description": "Merges a duplicate into a surviving incident, combining their timelines\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "source_id": {
+            "type": "string",
+            "description": "incident ID to merge away\n"
+          },
+          "survivor_id": {
+            "type": "string",
+            "description": "incident ID that survives the merge\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is merging\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "source_id",
+          "survivor_id",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "import_incidents",
+      "description": "Bulk-imports historical incidents (admin only). Each row is validated on its own and errors are reported per row; original IDs and timestamps are kept as external references.\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "batch": {
+            "type": "string",
+            "description": "JSON array of incidents, or CSV with a header row\n"
+          },
+          "format": {
+            "type": "string",
+            "description": "batch format (json, csv), empty for json\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is running the import\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "batch",
+          "format",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "grant_role",
+      "description": "Grants a role to a caller identity (admin only)\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "identity": {
+            "type": "string",
+            "description": "caller identity\n"
+          },
+          "role": {
+            "type": "string",
+            "description": "role (admin, incident_commander, responder, viewer)\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "identity",
+          "role",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "revoke_role",
+      "description": "Revokes a caller identity's role (admin only)\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "identity": {
+            "type": "string",
+            "description": "caller identity\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "identity",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_role",
+      "description": "Gets the role held by a caller identity\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "identity": {
+            "type": "string",
+            "description": "caller identity, empty for the current caller\n"
+          }
+        },
+        "required": [
+          "identity"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_access_denials",
+      "description": "Lists the most recent calls rejected for lacking a role. A rejected mutation fails with an \"Access denied\" error; its denial is kept only where the runtime commits state from failed calls\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "limit": {
+            "type": "integer",
+            "description": "number of entries, 0 for the default of 50\n"
+          }
+        },
+        "required": [
+          "limit"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "add_comment",
+      "description": "Adds a responder comment to an incident\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "author": {
+            "type": "string",
+            "description": "comment author\n"
+          },
+          "text": {
+            "type": "string",
+            "description": "comment text\n"
+          },
+          "visibility": {
+            "type": "string",
+            "description": "internal or public, empty for internal\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "author",
+          "text",
+          "visibility",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "edit_comment",
+      "description": "Edits a comment, keeping earlier versions as revisions\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "comment_id": {
+            "type": "string",
+            "description": "comment ID\n"
+          },
+          "text": {
+            "type": "string",
+            "description": "new comment text\n"
+          },
+          "editor": {
+            "type": "string",
+            "description": "who is editing\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "comment_id",
+          "text",
+          "editor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "list_comments",
+      "description": "Lists an incident's comments with their revision history\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "visibility": {
+            "type": "string",
+            "description": "internal or public, empty for all\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "visibility"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "attach_evidence",
+      "description": "Attaches evidence such as log excerpts, stack traces or screenshots (max 1 MiB each, 10 MiB per incident)\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "name": {
+            "type": "string",
+            "description": "file name\n"
+          },
+          "content_type": {
+            "type": "string",
+            "description": "MIME type, e.g. text/plain or image/png\n"
+          },
+          "content": {
+            "type": "string",
+            "description": "evidence content\n"
+          },
+          "encoding": {
+            "type": "string",
+            "description": "content encoding (text, base64), empty for text\n"
+          },
+          "uploader": {
+            "type": "string",
+            "description": "who is uploading\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "name",
+          "content_type",
+          "content",
+          "encoding",
+          "uploader",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "list_evidence",
+      "description": "Lists evidence attached to an incident\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          }
+        },
+        "required": [
+          "incident_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_evidence",
+      "description": "Streams an evidence blob by its sha256 hash\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "hash": {
+            "type": "string",
+            "description": "sha256 hash of the evidence\n"
+          }
+        },
+        "required": [
+          "hash"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "add_action_item",
+      "description": "Files a follow-up action item on an incident\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "title": {
+            "type": "string",
+            "description": "what needs to be done\n"
+          },
+          "owner": {
+            "type": "string",
+            "description": "who owns the item, empty if unassigned\n"
+          },
+          "due_date": {
+            "type": "string",
+            "description": "due date (RFC 3339 or YYYY-MM-DD), empty for none\n"
+          },
+          "priority": {
+            "type": "string",
+            "description": "priority P0-P3\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is filing the item\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "title",
+          "owner",
+          "due_date",
+          "priority",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "update_action_item",
+      "description": "Updates an action item's owner, due date, priority or status; every change is kept in its history\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "item_id": {
+            "type": "string",
+            "description": "action item ID, e.g. A1\n"
+          },
+          "owner": {
+            "type": "string",
+            "description": "new owner, empty to leave unchanged, - to unassign\n"
+          },
+          "due_date": {
+            "type": "string",
+            "description": "new due date (RFC 3339 or YYYY-MM-DD), empty to leave unchanged, - to clear\n"
+          },
+          "priority": {
+            "type": "string",
+            "description": "new priority P0-P3, empty to leave unchanged\n"
+          },
+          "status": {
+            "type": "string",
+            "description": "new status (open, in_progress, done, wont_do), empty to leave unchanged\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is updating the item\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "item_id",
+          "owner",
+          "due_date",
+          "priority",
+          "status",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "list_action_items",
+      "description": "Lists an incident's action items\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "status": {
+            "type": "string",
+            "description": "status (open, in_progress, done, wont_do), empty for all\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "status"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_overdue_action_items",
+      "description": "Lists open action items past their due date across all incidents, highest priority first\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "as_of": {
+            "type": "string",
+            "description": "RFC 3339 time to check against, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "as_of"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "update_impact",
+      "description": "Records customer impact on an incident; every change is kept in its impact history\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "started_at": {
+            "type": "string",
+            "description": "when customer impact started (RFC 3339), empty to leave unchanged, - to clear\n"
+          },
+          "ended_at": {
+            "type": "string",
+            "description": "when customer impact ended (RFC 3339), empty to leave unchanged, - to clear\n"
+          },
+          "regions": {
+            "type": "string",
+            "description": "comma-separated affected regions, empty to leave unchanged, - to clear\n"
+          },
+          "affected_users": {
+            "type": "string",
+            "description": "estimated number of affected users, empty to leave unchanged, - to clear\n"
+          },
+          "components": {
+            "type": "string",
+            "description": "comma-separated impacted components, empty to leave unchanged, - to clear\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is recording the impact\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "started_at",
+          "ended_at",
+          "regions",
+          "affected_users",
+          "components",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "add_labels",
+      "description": "Adds or updates key/value labels on an incident\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "labels": {
+            "type": "string",
+            "description": "comma-separated key=value pairs, e.g. region=eu-west,tier=enterprise\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is labelling\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "labels",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "remove_labels",
+      "description": "Removes labels from an incident by key\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "keys": {
+            "type": "string",
+            "description": "comma-separated label keys\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is removing the labels\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "keys",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_incident_timeline",
+      "description": "Retrieves complete incident timeline, including comments, in chronological order, with the recorded customer impact\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          }
+        },
+        "required": [
+          "incident_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "verify_incident_integrity",
+      "description": "Recomputes the incident's log hash chain and reports the first broken link\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          }
+        },
+        "required": [
+          "incident_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_merkle_root",
+      "description": "Exports the Merkle root over the incident's log entry hashes for external attestation\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          }
+        },
+        "required": [
+          "incident_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "generate_postmortem",
+      "description": "Renders a post-incident report from the stored incident and timeline\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "format": {
+            "type": "string",
+            "description": "output format (markdown, json)\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "format"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "search_incidents",
+      "description": "Full-text search over descriptions, status change notes, comments and action details, best matches first\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "query": {
+            "type": "string",
+            "description": "words or phrase to search for, e.g. connection pool exhausted\n"
+          },
+          "limit": {
+            "type": "integer",
+            "description": "maximum number of incidents, 0 for the default of 10 (max 50)\n"
+          }
+        },
+        "required": [
+          "query",
+          "limit"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_active_incidents",
+      "description": "Gets all active incidents, unassigned first\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "query_incidents",
+      "description": "Searches incidents with filters and cursor-based pagination\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "severity": {
+            "type": "string",
+            "description": "comma-separated severities to include, empty for all\n"
+          },
+          "service": {
+            "type": "string",
+            "description": "affected service, empty for all\n"
+          },
+          "status": {
+            "type": "string",
+            "description": "comma-separated statuses to include, empty for all; merged incidents match no status\n"
+          },
+          "reporter": {
+            "type": "string",
+            "description": "reporter name, empty for all\n"
+          },
+          "created_from": {
+            "type": "string",
+            "description": "earliest created_at to include (inclusive), empty for no bound\n"
+          },
+          "created_to": {
+            "type": "string",
+            "description": "latest created_at to include (exclusive), empty for no bound\n"
+          },
+          "labels": {
+            "type": "string",
+            "description": "label selector such as region=eu-west,tier!=free, empty for all\n"
+          },
+          "cursor": {
+            "type": "string",
+            "description": "next_cursor from the previous page, empty for the first page\n"
+          },
+          "limit": {
+            "type": "integer",
+            "description": "page size, 0 for the default of 25 (max 100)\n"
+          }
+        },
+        "required": [
+          "severity",
+          "service",
+          "status",
+          "reporter",
+          "created_from",
+          "created_to",
+          "labels",
+          "cursor",
+          "limit"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "export_incident_data",
+      "description": "Exports one chunk of incident history as CSV or NDJSON for warehouse loading\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "dataset": {
+            "type": "string",
+            "description": "dataset to export (incidents, transitions, actions)\n"
+          },
+          "format": {
+            "type": "string",
+            "description": "output format (csv, ndjson), empty for csv\n"
+          },
+          "from": {
+            "type": "string",
+            "description": "window start (inclusive, RFC 3339), empty for no bound\n"
+          },
+          "to": {
+            "type": "string",
+            "description": "window end (exclusive, RFC 3339), empty for no bound\n"
+          },
+          "cursor": {
+            "type": "string",
+            "description": "next_cursor from the previous chunk, empty for the first chunk\n"
+          },
+          "limit": {
+            "type": "integer",
+            "description": "rows per chunk, 0 for the default of 500 (max 2000)\n"
+          }
+        },
+        "required": [
+          "dataset",
+          "format",
+          "from",
+          "to",
+          "cursor",
+          "limit"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_export_schemas",
+      "description": "Lists the versioned column schemas used by export_incident_data\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_response_metrics",
+      "description": "Computes MTTA, MTTM and MTTR for incidents reported in a time window, grouped by service and severity. Merged duplicates are left out\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "from": {
+            "type": "string",
+            "description": "window start (inclusive, RFC 3339), empty for no bound\n"
+          },
+          "to": {
+            "type": "string",
+            "description": "window end (exclusive, RFC 3339), empty for no bound\n"
+          }
+        },
+        "required": [
+          "from",
+          "to"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "plot_incidents_per_day",
+      "description": "Plots incidents reported per day, one series per severity\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "from": {
+            "type": "string",
+            "description": "window start (RFC 3339), empty for the first incident or at most 366 days before the end\n"
+          },
+          "to": {
+            "type": "string",
+            "description": "window end (RFC 3339), empty for the latest activity\n"
+          }
+        },
+        "required": [
+          "from",
+          "to"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "plot_open_incidents",
+      "description": "Plots the number of open incidents at the end of each day; a merged duplicate stops counting once merged\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "from": {
+            "type": "string",
+            "description": "window start (RFC 3339), empty for the first incident or at most 366 days before the end\n"
+          },
+          "to": {
+            "type": "string",
+            "description": "window end (RFC 3339), empty for the latest activity\n"
+          }
+        },
+        "required": [
+          "from",
+          "to"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "plot_mttr_per_week",
+      "description": "Plots mean time to resolve in minutes per week of resolution\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "from": {
+            "type": "string",
+            "description": "window start (RFC 3339), empty for the first incident or at most 366 days before the end\n"
+          },
+          "to": {
+            "type": "string",
+            "description": "window end (RFC 3339), empty for the latest activity\n"
+          }
+        },
+        "required": [
+          "from",
+          "to"
+        ]
       }
     }
   }
@@ -0,0 +1,113 @@
+use crate::IncidentTrackerContractState;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use weil_rs::runtime::Runtime;
+
+// Roles are ordered by privilege; each role can do everything the ones below it can
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Viewer,
+    Responder,
+    IncidentCommander,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Viewer => "viewer",
+            Role::Responder => "responder",
+            Role::IncidentCommander => "incident_commander",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace([' ', '-'], "_").as_str() {
+            "viewer" => Ok(Role::Viewer),
+            "responder" => Ok(Role::Responder),
+            "incident_commander" | "commander" | "ic" => Ok(Role::IncidentCommander),
+            "admin" => Ok(Role::Admin),
+            _ => Err(format!(
+                "Unknown role '{}' (expected admin, incident_commander, responder, viewer)",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessDenial {
+    pub caller: String,
+    pub method: String,
+    pub required: Role,
+    pub held: Option<Role>,
+    pub timestamp: String,
+}
+
+impl IncidentTrackerContractState {
+    pub(crate) fn role_of(&self, identity: &str) -> Option<Role> {
+        self.roles.get(&identity.to_string())
+    }
+
+    // Checks that the calling identity holds at least `required`, recording
+    // the attempt in the access log when it does not. Returns the caller.
+    // The denial is written before the mutation fails with Err, so it is kept
+    // only if the runtime commits state written by failed calls.
+    pub(crate) fn authorize(&mut self, method: &str, required: Role, timestamp: &str) -> Result<String, String> {
+        let caller = Runtime::sender();
+        let held = self.role_of(&caller);
+        if held.is_some_and(|role| role >= required) {
+            return Ok(caller);
+        }
+
+        self.access_denials.push(AccessDenial {
+            caller: caller.clone(),
+            method: method.to_string(),
+            required,
+            held,
+            timestamp: timestamp.to_string(),
+        });
+        Err(format!(
+            "Access denied: {} requires the {} role, caller {} has {}",
+            method,
+            required,
+            caller,
+            held.map_or("no role".to_string(), |role| role.to_string())
+        ))
+    }
+
+    pub(crate) fn set_role(&mut self, identity: &str, role: Option<Role>) -> Result<(), String> {
+        let previous = self.role_of(identity);
+        if previous == Some(Role::Admin) && role != Some(Role::Admin) && self.admin_count <= 1 {
+            return Err("Cannot remove the last admin".to_string());
+        }
+
+        match role {
+            Some(role) => self.roles.insert(identity.to_string(), role),
+            None => {
+                self.roles.remove(&identity.to_string());
+            }
+        }
+
+        if previous == Some(Role::Admin) {
+            self.admin_count -= 1;
+        }
+        if role == Some(Role::Admin) {
+            self.admin_count += 1;
+        }
+        Ok(())
+    }
+}
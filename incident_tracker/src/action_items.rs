@@ -0,0 +1,160 @@
+use crate::history::{field_changes, FieldChange, CLEAR};
+use crate::time::normalize_timestamp;
+use crate::IncidentTrackerContractState;
+use incident_common::Severity;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemStatus {
+    Open,
+    InProgress,
+    Done,
+    WontDo,
+}
+
+impl ItemStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ItemStatus::Open => "open",
+            ItemStatus::InProgress => "in_progress",
+            ItemStatus::Done => "done",
+            ItemStatus::WontDo => "wont_do",
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        matches!(self, ItemStatus::Open | ItemStatus::InProgress)
+    }
+}
+
+impl fmt::Display for ItemStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ItemStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace(['-', ' '], "_").as_str() {
+            "open" | "todo" => Ok(ItemStatus::Open),
+            "in_progress" => Ok(ItemStatus::InProgress),
+            "done" | "completed" => Ok(ItemStatus::Done),
+            "wont_do" | "wontdo" | "cancelled" => Ok(ItemStatus::WontDo),
+            _ => Err(format!("Unknown action item status '{}' (expected open, in_progress, done or wont_do)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub id: String,
+    pub incident_id: String,
+    pub title: String,
+    pub owner: Option<String>,
+    pub due_date: Option<String>,
+    // P0-P3, on the same scale as incident severity
+    pub priority: Severity,
+    pub status: ItemStatus,
+    pub created_by: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub completed_at: Option<String>,
+    pub history: Vec<FieldChange>,
+}
+
+impl ActionItem {
+    pub fn is_overdue(&self, as_of: &str) -> bool {
+        self.status.is_pending() && self.due_date.as_deref().is_some_and(|due| due < as_of)
+    }
+}
+
+// Arguments to update_action_item: empty leaves a field unchanged and "-"
+// clears the owner or due date
+pub struct ActionItemUpdate {
+    pub owner: String,
+    pub due_date: String,
+    pub priority: String,
+    pub status: String,
+}
+
+pub fn action_item_key(incident_id: &str, item_id: &str) -> String {
+    format!("incident:{}:action_item:{}", incident_id, item_id)
+}
+
+pub fn parse_owner(owner: &str) -> Option<String> {
+    match owner.trim() {
+        "" | CLEAR => None,
+        owner => Some(owner.to_string()),
+    }
+}
+
+// Accepts an RFC 3339 timestamp or a plain date, which is due by the end of that day (UTC)
+pub fn parse_due_date(due_date: &str) -> Result<Option<String>, String> {
+    match due_date.trim() {
+        "" | CLEAR => Ok(None),
+        date if date.len() == 10 => normalize_timestamp(&format!("{}T23:59:59Z", date)).map(Some),
+        timestamp => normalize_timestamp(timestamp).map(Some),
+    }
+}
+
+impl ActionItemUpdate {
+    // Applies the update to a copy of the item, recording one change per field that differs
+    pub fn apply(&self, item: &ActionItem, actor: &str, timestamp: &str) -> Result<ActionItem, String> {
+        let mut next = item.clone();
+        if !self.owner.trim().is_empty() {
+            next.owner = parse_owner(&self.owner);
+        }
+        if !self.due_date.trim().is_empty() {
+            next.due_date = parse_due_date(&self.due_date)?;
+        }
+        if !self.priority.trim().is_empty() {
+            next.priority = self.priority.parse()?;
+        }
+        if !self.status.trim().is_empty() {
+            next.status = self.status.parse()?;
+        }
+
+        let fields = [
+            ("owner", json!(item.owner), json!(next.owner)),
+            ("due_date", json!(item.due_date), json!(next.due_date)),
+            ("priority", json!(item.priority), json!(next.priority)),
+            ("status", json!(item.status), json!(next.status)),
+        ];
+        let changes = field_changes(fields, actor, timestamp);
+        if changes.is_empty() {
+            return Err(format!("Action item {} is unchanged", item.id));
+        }
+
+        next.completed_at = match (item.status.is_pending(), next.status.is_pending()) {
+            (true, false) => Some(timestamp.to_string()),
+            (_, true) => None,
+            (false, false) => item.completed_at.clone(),
+        };
+        next.updated_at = timestamp.to_string();
+        next.history.extend(changes);
+        Ok(next)
+    }
+}
+
+impl IncidentTrackerContractState {
+    pub(crate) fn load_action_items(&self, incident_id: &str, count: u64) -> Vec<ActionItem> {
+        (1..=count)
+            .filter_map(|n| self.action_items.get(&action_item_key(incident_id, &format!("A{}", n))))
+            .collect()
+    }
+
+    // P0 follow-ups must have an owner before the incident can be signed off
+    pub(crate) fn unassigned_p0_items(&self, incident_id: &str, count: u64) -> Vec<String> {
+        self.load_action_items(incident_id, count)
+            .into_iter()
+            .filter(|item| item.priority == Severity::P0 && item.status.is_pending() && item.owner.is_none())
+            .map(|item| item.id)
+            .collect()
+    }
+}
@@ -0,0 +1,158 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+// Payload schemas for log_action, one per action type. Unknown fields are
+// rejected so that timelines stay machine-readable.
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Notified {
+    // discord, slack, sms or email
+    pub channel: String,
+    #[serde(default)]
+    pub recipients: Vec<String>,
+    #[serde(default)]
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Escalated {
+    pub to: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Paged {
+    pub responder: String,
+    #[serde(default)]
+    pub via: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WarRoomCreated {
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatusPageUpdated {
+    pub status: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemediationApplied {
+    pub action: String,
+    #[serde(default)]
+    pub outcome: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Comment {
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum Action {
+    Notified(Notified),
+    Escalated(Escalated),
+    Paged(Paged),
+    WarRoomCreated(WarRoomCreated),
+    StatusPageUpdated(StatusPageUpdated),
+    RemediationApplied(RemediationApplied),
+    Comment(Comment),
+}
+
+pub const ACTION_TYPES: [&str; 7] = [
+    "notified",
+    "escalated",
+    "paged",
+    "war_room_created",
+    "status_page_updated",
+    "remediation_applied",
+    "comment",
+];
+
+const NOTIFY_CHANNELS: [&str; 4] = ["discord", "slack", "sms", "email"];
+
+fn payload<T: DeserializeOwned>(action_type: &str, details: &str) -> Result<T, String> {
+    serde_json::from_str(details).map_err(|e| format!("Invalid details for '{}': {}", action_type, e))
+}
+
+fn required(action_type: &str, field: &str, value: &str) -> Result<(), String> {
+    if value.trim().is_empty() {
+        Err(format!("Invalid details for '{}': `{}` must not be empty", action_type, field))
+    } else {
+        Ok(())
+    }
+}
+
+impl Action {
+    // Parses log_action's action_type and details JSON into a typed action
+    pub fn parse(action_type: &str, details: &str) -> Result<Action, String> {
+        let action = match action_type {
+            "notified" => {
+                let mut p: Notified = payload(action_type, details)?;
+                // stored normalized, so "Slack" and "slack" read back the same
+                p.channel = p.channel.trim().to_lowercase();
+                if !NOTIFY_CHANNELS.contains(&p.channel.as_str()) {
+                    return Err(format!(
+                        "Invalid details for 'notified': unknown channel '{}' (expected one of: {})",
+                        p.channel,
+                        NOTIFY_CHANNELS.join(", ")
+                    ));
+                }
+                Action::Notified(p)
+            }
+            "escalated" => {
+                let p: Escalated = payload(action_type, details)?;
+                required(action_type, "to", &p.to)?;
+                Action::Escalated(p)
+            }
+            "paged" => {
+                let p: Paged = payload(action_type, details)?;
+                required(action_type, "responder", &p.responder)?;
+                Action::Paged(p)
+            }
+            "war_room_created" => {
+                let p: WarRoomCreated = payload(action_type, details)?;
+                required(action_type, "url", &p.url)?;
+                Action::WarRoomCreated(p)
+            }
+            "status_page_updated" => {
+                let p: StatusPageUpdated = payload(action_type, details)?;
+                required(action_type, "status", &p.status)?;
+                Action::StatusPageUpdated(p)
+            }
+            "remediation_applied" => {
+                let p: RemediationApplied = payload(action_type, details)?;
+                required(action_type, "action", &p.action)?;
+                Action::RemediationApplied(p)
+            }
+            "comment" => {
+                let p: Comment = payload(action_type, details)?;
+                required(action_type, "text", &p.text)?;
+                Action::Comment(p)
+            }
+            _ => {
+                return Err(format!(
+                    "Unknown action type '{}' (expected one of: {})",
+                    action_type,
+                    ACTION_TYPES.join(", ")
+                ))
+            }
+        };
+        Ok(action)
+    }
+
+    // Canonical JSON for the timeline, with defaults filled in
+    pub fn to_details(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
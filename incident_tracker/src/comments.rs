@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    // responders only
+    Internal,
+    // safe to share in customer-facing updates
+    Public,
+}
+
+impl FromStr for Visibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "internal" => Ok(Visibility::Internal),
+            "public" => Ok(Visibility::Public),
+            _ => Err(format!("Unknown visibility '{}' (expected internal or public)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentRevision {
+    pub text: String,
+    pub edited_by: String,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentComment {
+    pub id: String,
+    pub incident_id: String,
+    pub author: String,
+    // caller identity that wrote the comment, allowed to edit it later
+    pub author_identity: String,
+    pub visibility: Visibility,
+    pub text: String,
+    pub created_at: String,
+    pub edited_at: Option<String>,
+    // earlier versions of the text, oldest first
+    pub revisions: Vec<CommentRevision>,
+}
+
+pub fn comment_key(incident_id: &str, comment_id: &str) -> String {
+    format!("incident:{}:comment:{}", incident_id, comment_id)
+}
@@ -0,0 +1,45 @@
+use crate::integrity::sha256_hex;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_ATTACHMENT_BYTES: usize = 1024 * 1024;
+pub const MAX_INCIDENT_EVIDENCE_BYTES: u64 = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    // sha256 of the content, also the key it is stored under
+    pub hash: String,
+    pub name: String,
+    pub content_type: String,
+    pub size: u64,
+    pub uploaded_by: String,
+    pub uploaded_at: String,
+}
+
+// Decodes an upload sent over MCP, where binary evidence such as screenshots
+// arrives base64 encoded and text evidence may be sent as-is
+pub fn decode_content(content: &str, encoding: &str) -> Result<Vec<u8>, String> {
+    let bytes = match encoding.trim().to_lowercase().as_str() {
+        "" | "text" | "utf8" => content.as_bytes().to_vec(),
+        "base64" => base64::engine::general_purpose::STANDARD
+            .decode(content.trim())
+            .map_err(|e| format!("Invalid base64 content: {}", e))?,
+        _ => return Err(format!("Unknown encoding '{}' (expected text or base64)", encoding)),
+    };
+
+    if bytes.is_empty() {
+        return Err("Evidence content must not be empty".to_string());
+    }
+    if bytes.len() > MAX_ATTACHMENT_BYTES {
+        return Err(format!(
+            "Evidence is {} bytes; attachments are limited to {} bytes",
+            bytes.len(),
+            MAX_ATTACHMENT_BYTES
+        ));
+    }
+    Ok(bytes)
+}
+
+pub fn content_hash(bytes: &[u8]) -> String {
+    sha256_hex(bytes)
+}
@@ -0,0 +1,333 @@
+use crate::labels::Labels;
+use crate::time::normalize_timestamp;
+use crate::{Incident, IncidentTrackerContractState, StatusTransition, TimelineEntry};
+use incident_common::IncidentStatus;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::str::FromStr;
+
+pub const DEFAULT_CHUNK_SIZE: usize = 500;
+pub const MAX_CHUNK_SIZE: usize = 2000;
+
+// Column layouts are a contract with downstream loaders. Columns are only ever
+// appended, never renamed, removed or retyped, and every change bumps the version.
+pub struct Schema {
+    pub version: u32,
+    pub columns: &'static [&'static str],
+}
+
+const INCIDENTS_V1: Schema = Schema {
+    version: 1,
+    columns: &[
+        "incident_id",
+        "created_at",
+        "updated_at",
+        "severity",
+        "status",
+        "service",
+        "reporter",
+        "assignee",
+        "acknowledged_at",
+        "resolved_at",
+        "closed_at",
+        "parent_id",
+        "merged_into",
+        "impact_started_at",
+        "impact_ended_at",
+        "affected_users",
+        "regions",
+        "components",
+        "labels",
+        "description",
+        "resolution_notes",
+        "external_source",
+        "external_id",
+    ],
+};
+
+const TRANSITIONS_V1: Schema = Schema {
+    version: 1,
+    columns: &["incident_id", "seq", "timestamp", "from_status", "to_status", "actor", "notes"],
+};
+
+const ACTIONS_V1: Schema = Schema {
+    version: 1,
+    columns: &["incident_id", "seq", "timestamp", "event", "details", "merged_from", "hash"],
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Dataset {
+    Incidents,
+    Transitions,
+    Actions,
+}
+
+impl Dataset {
+    pub const ALL: [Dataset; 3] = [Dataset::Incidents, Dataset::Transitions, Dataset::Actions];
+
+    pub fn schema(&self) -> &'static Schema {
+        match self {
+            Dataset::Incidents => &INCIDENTS_V1,
+            Dataset::Transitions => &TRANSITIONS_V1,
+            Dataset::Actions => &ACTIONS_V1,
+        }
+    }
+}
+
+impl FromStr for Dataset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "incidents" => Ok(Dataset::Incidents),
+            "transitions" => Ok(Dataset::Transitions),
+            "actions" | "action_logs" => Ok(Dataset::Actions),
+            _ => Err(format!("Unknown dataset '{}' (expected incidents, transitions or actions)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Ndjson,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "csv" => Ok(ExportFormat::Csv),
+            "ndjson" | "jsonl" => Ok(ExportFormat::Ndjson),
+            _ => Err(format!("Unknown export format '{}' (expected csv or ndjson)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Chunk {
+    pub dataset: Dataset,
+    pub format: ExportFormat,
+    pub schema_version: u32,
+    pub columns: &'static [&'static str],
+    pub rows: usize,
+    // CSV chunks carry the header row only when the cursor was empty
+    pub data: String,
+    pub next_cursor: Option<String>,
+}
+
+// A row and the key it is ordered and resumed by. Keys follow write order
+// (creation order for incidents, log write order otherwise) rather than
+// timestamps, which merges and imports backdate, so rows written after an
+// export started always sort after the cursor.
+struct Row {
+    key: String,
+    timestamp: String,
+    values: Vec<Value>,
+}
+
+fn first_transition_at(incident: &Incident, status: IncidentStatus) -> Option<&str> {
+    incident
+        .status_history
+        .iter()
+        .find(|t| t.to == status)
+        .map(|t| t.timestamp.as_str())
+}
+
+fn joined_labels(labels: &Labels) -> String {
+    labels
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn entry_key(entry: &TimelineEntry) -> String {
+    format!("{:012}", entry.write_seq)
+}
+
+fn incident_row(index: usize, incident: &Incident) -> Row {
+    Row {
+        key: format!("{:012}", index),
+        timestamp: incident.created_at.clone(),
+        values: vec![
+            json!(incident.id),
+            json!(incident.created_at),
+            json!(incident.updated_at),
+            json!(incident.severity),
+            json!(incident.status),
+            json!(incident.service),
+            json!(incident.reporter),
+            json!(incident.assignee),
+            json!(incident.acknowledged_at),
+            json!(first_transition_at(incident, IncidentStatus::Resolved)),
+            json!(first_transition_at(incident, IncidentStatus::Closed)),
+            json!(incident.link.as_ref().map(|link| &link.target)),
+            json!(incident.merged_into),
+            json!(incident.impact.started_at),
+            json!(incident.impact.ended_at),
+            json!(incident.impact.affected_users),
+            json!(incident.impact.regions.join(";")),
+            json!(incident.impact.components.join(";")),
+            json!(joined_labels(&incident.labels)),
+            json!(incident.description),
+            json!(incident.notes),
+            json!(incident.external_ref.as_ref().map(|r| &r.source)),
+            json!(incident.external_ref.as_ref().map(|r| &r.id)),
+        ],
+    }
+}
+
+fn csv_field(value: &Value) -> String {
+    let text = match value {
+        Value::Null => return String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+fn csv_line(values: &[Value]) -> String {
+    values.iter().map(csv_field).collect::<Vec<_>>().join(",")
+}
+
+// Optional RFC 3339 bound, normalized so it compares with stored timestamps
+fn bound(value: &str) -> Result<Option<String>, String> {
+    match value.trim() {
+        "" => Ok(None),
+        value => normalize_timestamp(value).map(Some),
+    }
+}
+
+impl IncidentTrackerContractState {
+    fn export_rows(&self, dataset: Dataset) -> Vec<Row> {
+        let incidents = self.all_incidents();
+        match dataset {
+            Dataset::Incidents => incidents
+                .iter()
+                .enumerate()
+                .map(|(i, incident)| incident_row(i, incident))
+                .collect(),
+            // Read from the log rather than status_history so each transition
+            // carries its write order; copies from merged incidents are skipped
+            Dataset::Transitions => incidents
+                .iter()
+                .flat_map(|incident| self.load_chain(&incident.id).into_iter().flatten())
+                .filter(|entry| entry.merged_from.is_none())
+                .filter(|entry| matches!(entry.event.as_str(), "status_changed" | "reopened"))
+                .filter_map(|entry| {
+                    let t: StatusTransition = serde_json::from_str(&entry.details).ok()?;
+                    Some(Row {
+                        key: entry_key(&entry),
+                        timestamp: t.timestamp.clone(),
+                        values: vec![
+                            json!(entry.incident_id),
+                            json!(entry.seq),
+                            json!(t.timestamp),
+                            json!(t.from),
+                            json!(t.to),
+                            json!(t.actor),
+                            json!(t.notes),
+                        ],
+                    })
+                })
+                .collect(),
+            Dataset::Actions => incidents
+                .iter()
+                .flat_map(|incident| self.load_chain(&incident.id).into_iter().flatten())
+                .map(|entry| Row {
+                    key: entry_key(&entry),
+                    timestamp: entry.timestamp.clone(),
+                    values: vec![
+                        json!(entry.incident_id),
+                        json!(entry.seq),
+                        json!(entry.timestamp),
+                        json!(entry.event),
+                        json!(entry.details),
+                        json!(entry.merged_from),
+                        json!(entry.hash),
+                    ],
+                })
+                .collect(),
+        }
+    }
+
+    // One chunk of a dataset, restricted to rows timestamped in [from, to)
+    pub(crate) fn export_chunk(
+        &self,
+        dataset: &str,
+        format: &str,
+        from: &str,
+        to: &str,
+        cursor: &str,
+        limit: u32,
+    ) -> Result<Chunk, String> {
+        let dataset: Dataset = dataset.parse()?;
+        let format: ExportFormat = format.parse()?;
+        let (from, to) = (bound(from)?, bound(to)?);
+        let limit = match limit as usize {
+            0 => DEFAULT_CHUNK_SIZE,
+            n => n.min(MAX_CHUNK_SIZE),
+        };
+        let schema = dataset.schema();
+
+        let mut rows: Vec<Row> = self
+            .export_rows(dataset)
+            .into_iter()
+            .filter(|row| from.as_ref().is_none_or(|from| row.timestamp >= *from))
+            .filter(|row| to.as_ref().is_none_or(|to| row.timestamp < *to))
+            .filter(|row| cursor.is_empty() || row.key.as_str() > cursor)
+            .collect();
+        rows.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let next_cursor = if rows.len() > limit {
+            rows.truncate(limit);
+            rows.last().map(|row| row.key.clone())
+        } else {
+            None
+        };
+
+        let mut data = String::new();
+        match format {
+            ExportFormat::Csv => {
+                if cursor.is_empty() {
+                    let header: Vec<Value> = schema.columns.iter().map(|c| json!(c)).collect();
+                    data.push_str(&csv_line(&header));
+                    data.push('\n');
+                }
+                for row in &rows {
+                    data.push_str(&csv_line(&row.values));
+                    data.push('\n');
+                }
+            }
+            ExportFormat::Ndjson => {
+                for row in &rows {
+                    let mut object = Map::new();
+                    object.insert("schema_version".to_string(), json!(schema.version));
+                    for (column, value) in schema.columns.iter().zip(&row.values) {
+                        object.insert(column.to_string(), value.clone());
+                    }
+                    data.push_str(&Value::Object(object).to_string());
+                    data.push('\n');
+                }
+            }
+        }
+
+        Ok(Chunk {
+            dataset,
+            format,
+            schema_version: schema.version,
+            columns: schema.columns,
+            rows: rows.len(),
+            data,
+            next_cursor,
+        })
+    }
+}
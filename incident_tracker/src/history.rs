@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Update arguments use this to clear a field; empty leaves it unchanged
+pub const CLEAR: &str = "-";
+
+// One field of a record changing value, as kept in impact and action item histories
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: Value,
+    pub to: Value,
+    pub actor: String,
+    pub timestamp: String,
+}
+
+// One change per (field, from, to) whose values differ
+pub fn field_changes<const N: usize>(
+    fields: [(&str, Value, Value); N],
+    actor: &str,
+    timestamp: &str,
+) -> Vec<FieldChange> {
+    fields
+        .into_iter()
+        .filter(|(_, from, to)| from != to)
+        .map(|(field, from, to)| FieldChange {
+            field: field.to_string(),
+            from,
+            to,
+            actor: actor.to_string(),
+            timestamp: timestamp.to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,109 @@
+use crate::history::{field_changes, FieldChange, CLEAR};
+use crate::time::{normalize_timestamp, parse_timestamp};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+// Customer impact as recorded by responders, which often starts before the
+// incident is reported and ends before it is resolved
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Impact {
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
+    pub regions: Vec<String>,
+    pub affected_users: Option<u64>,
+    pub components: Vec<String>,
+}
+
+// Arguments to update_impact: empty leaves a field unchanged and "-" clears it
+pub struct ImpactUpdate {
+    pub started_at: String,
+    pub ended_at: String,
+    pub regions: String,
+    pub affected_users: String,
+    pub components: String,
+}
+
+fn parse_time(value: &str) -> Result<Option<String>, String> {
+    if value == CLEAR {
+        return Ok(None);
+    }
+    normalize_timestamp(value).map(Some)
+}
+
+fn parse_users(value: &str) -> Result<Option<u64>, String> {
+    if value == CLEAR {
+        return Ok(None);
+    }
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| format!("Invalid affected-user estimate '{}' (expected a whole number)", value))
+}
+
+// Comma-separated names, trimmed and de-duplicated in order
+fn parse_names(value: &str) -> Vec<String> {
+    if value == CLEAR {
+        return Vec::new();
+    }
+    let mut names: Vec<String> = Vec::new();
+    for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        if !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+impl Impact {
+    // Applies an update, returning the new impact. Times may not be later
+    // than `now`, and impact cannot end before it starts.
+    pub fn apply(&self, update: &ImpactUpdate, now: &str) -> Result<Impact, String> {
+        let mut next = self.clone();
+        let started_at = update.started_at.trim();
+        if !started_at.is_empty() {
+            next.started_at = parse_time(started_at)?;
+        }
+        let ended_at = update.ended_at.trim();
+        if !ended_at.is_empty() {
+            next.ended_at = parse_time(ended_at)?;
+        }
+        let regions = update.regions.trim();
+        if !regions.is_empty() {
+            next.regions = parse_names(regions);
+        }
+        let affected_users = update.affected_users.trim();
+        if !affected_users.is_empty() {
+            next.affected_users = parse_users(affected_users)?;
+        }
+        let components = update.components.trim();
+        if !components.is_empty() {
+            next.components = parse_names(components);
+        }
+
+        let now = parse_timestamp(now)?;
+        for at in next.started_at.iter().chain(&next.ended_at) {
+            if parse_timestamp(at)? > now {
+                return Err(format!("Impact time {} is later than the update itself", at));
+            }
+        }
+        match (&next.started_at, &next.ended_at) {
+            (Some(start), Some(end)) if end < start => {
+                Err(format!("Impact cannot end ({}) before it starts ({})", end, start))
+            }
+            (None, Some(_)) => Err("Impact end requires an impact start".to_string()),
+            _ => Ok(next),
+        }
+    }
+
+    // One change per field that differs between self and next
+    pub fn diff(&self, next: &Impact, actor: &str, timestamp: &str) -> Vec<FieldChange> {
+        let fields = [
+            ("started_at", json!(self.started_at), json!(next.started_at)),
+            ("ended_at", json!(self.ended_at), json!(next.ended_at)),
+            ("regions", json!(self.regions), json!(next.regions)),
+            ("affected_users", json!(self.affected_users), json!(next.affected_users)),
+            ("components", json!(self.components), json!(next.components)),
+        ];
+        field_changes(fields, actor, timestamp)
+    }
+}
@@ -0,0 +1,548 @@
+use crate::actions::{Action, ACTION_TYPES};
+use crate::impact::Impact;
+use crate::labels::{parse_labels, Labels, MAX_LABELS};
+use crate::search::{action_text, DocRef, Field};
+use crate::time::{normalize_timestamp, parse_timestamp};
+use crate::{Assignment, Incident, IncidentTrackerContractState};
+use incident_common::{IncidentStatus, Severity};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+pub const MAX_IMPORT_ROWS: usize = 500;
+const DEFAULT_SOURCE: &str = "import";
+// event name for imported timeline entries that are not log_action types
+const IMPORTED_EVENT: &str = "imported_event";
+
+// Where an imported incident came from, kept so it can be traced back to the
+// previous tool and so the same record is never imported twice
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalRef {
+    pub source: String,
+    pub id: String,
+    // the incident's creation time in the source system, as given
+    pub original_created_at: String,
+    pub imported_at: String,
+    pub imported_by: String,
+}
+
+pub fn external_ref_key(source: &str, id: &str) -> String {
+    format!("{}:{}", source, id)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImportTransition {
+    pub status: String,
+    pub timestamp: String,
+    #[serde(default)]
+    pub actor: String,
+    #[serde(default)]
+    pub notes: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImportEvent {
+    pub timestamp: String,
+    pub event: String,
+    #[serde(default)]
+    pub details: Value,
+}
+
+// One incident as supplied by the caller. CSV rows carry the flat fields
+// only; JSON rows may also carry a status history and timeline.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImportRow {
+    pub external_id: String,
+    #[serde(default)]
+    pub source: String,
+    pub description: String,
+    pub severity: String,
+    pub service: String,
+    pub reporter: String,
+    pub created_at: String,
+    // final status; with no transitions, resolved and closed are reached at
+    // resolved_at and any other status at created_at
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub resolved_at: String,
+    #[serde(default)]
+    pub resolution_notes: String,
+    #[serde(default)]
+    pub assignee: String,
+    // key=value pairs, as taken by add_labels
+    #[serde(default)]
+    pub labels: String,
+    #[serde(default)]
+    pub transitions: Vec<ImportTransition>,
+    #[serde(default)]
+    pub timeline: Vec<ImportEvent>,
+}
+
+pub struct ValidTransition {
+    pub to: IncidentStatus,
+    pub timestamp: String,
+    pub actor: String,
+    pub notes: String,
+}
+
+pub struct ValidEvent {
+    pub timestamp: String,
+    pub event: String,
+    pub details: String,
+}
+
+// A row that passed validation, with every timestamp normalized to UTC
+pub struct ValidImport {
+    pub source: String,
+    pub external_id: String,
+    pub original_created_at: String,
+    pub description: String,
+    pub severity: Severity,
+    pub service: String,
+    pub reporter: String,
+    pub created_at: String,
+    pub resolution_notes: String,
+    pub assignee: Option<String>,
+    pub labels: Labels,
+    pub transitions: Vec<ValidTransition>,
+    pub events: Vec<ValidEvent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RowResult {
+    // 1-based position in the batch, not counting a CSV header
+    pub row: usize,
+    pub external_id: Option<String>,
+    pub incident_id: Option<String>,
+    pub error: Option<String>,
+}
+
+fn required(value: String, field: &str) -> Result<String, String> {
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        return Err(format!("Missing {}", field));
+    }
+    Ok(value)
+}
+
+// A historical time: valid RFC 3339, not before `earliest` and not after `now`
+fn historical_time(value: &str, field: &str, earliest: Option<&str>, now: i64) -> Result<String, String> {
+    let timestamp = normalize_timestamp(value).map_err(|e| format!("{}: {}", field, e))?;
+    if parse_timestamp(&timestamp)? > now {
+        return Err(format!("{} {} is in the future", field, timestamp));
+    }
+    if let Some(earliest) = earliest {
+        if timestamp.as_str() < earliest {
+            return Err(format!("{} {} is earlier than {}", field, timestamp, earliest));
+        }
+    }
+    Ok(timestamp)
+}
+
+impl ImportRow {
+    pub fn validate(self, now: i64) -> Result<ValidImport, String> {
+        let external_id = required(self.external_id, "external_id")?;
+        let source = match self.source.trim() {
+            "" => DEFAULT_SOURCE.to_string(),
+            source => source.to_string(),
+        };
+        let description = required(self.description, "description")?;
+        let severity: Severity = required(self.severity, "severity")?.parse()?;
+        let service = required(self.service, "service")?;
+        let reporter = required(self.reporter, "reporter")?;
+        let original_created_at = required(self.created_at, "created_at")?;
+        let created_at = historical_time(&original_created_at, "created_at", None, now)?;
+        let labels = match self.labels.trim() {
+            "" => Labels::new(),
+            labels => parse_labels(labels)?,
+        };
+        if labels.len() > MAX_LABELS {
+            return Err(format!("{} labels given; the limit is {}", labels.len(), MAX_LABELS));
+        }
+        let assignee = Some(self.assignee.trim().to_string()).filter(|a| !a.is_empty());
+        let status: Option<IncidentStatus> = match self.status.trim() {
+            "" => None,
+            status => Some(status.parse()?),
+        };
+
+        let mut supplied = self.transitions;
+        if supplied.is_empty() {
+            // Flat rows only give a final status, reached directly from open
+            let steps: &[IncidentStatus] = match status {
+                None | Some(IncidentStatus::Open) => &[],
+                Some(IncidentStatus::Closed) => &[IncidentStatus::Resolved, IncidentStatus::Closed],
+                Some(ref status) => std::slice::from_ref(status),
+            };
+            let reached_at = match status {
+                Some(status) if !status.is_active() => {
+                    if self.resolved_at.trim().is_empty() {
+                        return Err(format!("resolved_at is required for status {}", status));
+                    }
+                    self.resolved_at.clone()
+                }
+                _ => original_created_at.clone(),
+            };
+            supplied = steps
+                .iter()
+                .map(|step| ImportTransition {
+                    status: step.to_string(),
+                    timestamp: reached_at.clone(),
+                    actor: String::new(),
+                    notes: String::new(),
+                })
+                .collect();
+        }
+
+        let mut transitions = Vec::new();
+        let mut current = IncidentStatus::Open;
+        let mut last = created_at.clone();
+        for (i, transition) in supplied.into_iter().enumerate() {
+            let to: IncidentStatus = transition.status.parse()?;
+            if !current.can_transition_to(to) {
+                return Err(format!("transitions[{}]: illegal status transition {} -> {}", i, current, to));
+            }
+            let timestamp = historical_time(&transition.timestamp, &format!("transitions[{}].timestamp", i), Some(&last), now)?;
+            let actor = match transition.actor.trim() {
+                "" => reporter.clone(),
+                actor => actor.to_string(),
+            };
+            current = to;
+            last = timestamp.clone();
+            transitions.push(ValidTransition { to, timestamp, actor, notes: transition.notes });
+        }
+        if status.is_some_and(|status| status != current) {
+            return Err(format!("status {} does not match the last transition ({})", self.status.trim(), current));
+        }
+
+        let mut events = Vec::new();
+        for (i, event) in self.timeline.into_iter().enumerate() {
+            let timestamp = historical_time(&event.timestamp, &format!("timeline[{}].timestamp", i), Some(&created_at), now)?;
+            let name = event.event.trim().to_lowercase();
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!("timeline[{}]: invalid event name '{}'", i, event.event));
+            }
+            
+            // Actions go through the same typed schemas as log_action. Anything
+            // else is wrapped as imported_event, so an import can never forge
+            // entries the contract writes itself, such as status_changed.
+            let (name, details) = if ACTION_TYPES.contains(&name.as_str()) {
+                let details = match event.details {
+                    Value::String(details) => details,
+                    details => details.to_string(),
+                };
+                let action = Action::parse(&name, &details).map_err(|e| format!("timeline[{}]: {}", i, e))?;
+                (name, action.to_details())
+            } else {
+                let details = json!({ "event": name, "details": event.details });
+                (IMPORTED_EVENT.to_string(), details.to_string())
+            };
+            events.push(ValidEvent { timestamp, event: name, details });
+        }
+
+        Ok(ValidImport {
+            source,
+            external_id,
+            original_created_at,
+            description,
+            severity,
+            service,
+            reporter,
+            created_at,
+            resolution_notes: self.resolution_notes,
+            assignee,
+            labels,
+            transitions,
+            events,
+        })
+    }
+}
+
+// Splits CSV text into records, following RFC 4180 quoting. Blank lines are
+// kept as records with one empty field so row numbers stay aligned with the
+// input; only the empty line after a final line break is dropped.
+fn parse_csv(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => record.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (c, _) => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("CSV ends inside a quoted field".to_string());
+    }
+    if !record.is_empty() || !field.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+const CSV_COLUMNS: &[&str] = &[
+    "external_id",
+    "source",
+    "description",
+    "severity",
+    "service",
+    "reporter",
+    "created_at",
+    "status",
+    "resolved_at",
+    "resolution_notes",
+    "assignee",
+    "labels",
+];
+
+fn csv_rows(batch: &str) -> Result<Vec<Result<ImportRow, String>>, String> {
+    let mut records = parse_csv(batch)?.into_iter();
+    let header: Vec<String> = records
+        .next()
+        .ok_or_else(|| "CSV batch is empty".to_string())?
+        .into_iter()
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+    if header.iter().all(String::is_empty) {
+        return Err("CSV header row is blank".to_string());
+    }
+    if let Some(unknown) = header.iter().find(|column| !CSV_COLUMNS.contains(&column.as_str())) {
+        return Err(format!("Unknown CSV column '{}' (expected {})", unknown, CSV_COLUMNS.join(", ")));
+    }
+
+    Ok(records
+        .map(|record| {
+            if record.iter().all(|field| field.trim().is_empty()) {
+                return Err("Blank row".to_string());
+            }
+            if record.len() != header.len() {
+                return Err(format!("Expected {} fields, found {}", header.len(), record.len()));
+            }
+            let mut fields: BTreeMap<&str, String> = header.iter().map(String::as_str).zip(record).collect();
+            let mut take = |column: &str| fields.remove(column).unwrap_or_default();
+            Ok(ImportRow {
+                external_id: take("external_id"),
+                source: take("source"),
+                description: take("description"),
+                severity: take("severity"),
+                service: take("service"),
+                reporter: take("reporter"),
+                created_at: take("created_at"),
+                status: take("status"),
+                resolved_at: take("resolved_at"),
+                resolution_notes: take("resolution_notes"),
+                assignee: take("assignee"),
+                labels: take("labels"),
+                ..ImportRow::default()
+            })
+        })
+        .collect())
+}
+
+fn json_rows(batch: &str) -> Result<Vec<Result<ImportRow, String>>, String> {
+    let rows: Vec<Value> = serde_json::from_str(batch).map_err(|e| format!("Invalid JSON batch: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .map(|row| serde_json::from_value(row).map_err(|e| format!("Invalid row: {}", e)))
+        .collect())
+}
+
+// Parses a batch into rows. A malformed batch fails as a whole; a malformed
+// row only fails that row.
+pub fn parse_batch(batch: &str, format: &str) -> Result<Vec<Result<ImportRow, String>>, String> {
+    let rows = match format.trim().to_lowercase().as_str() {
+        "" | "json" => json_rows(batch)?,
+        "csv" => csv_rows(batch)?,
+        _ => return Err(format!("Unknown import format '{}' (expected json or csv)", format)),
+    };
+    if rows.is_empty() {
+        return Err("Import batch has no rows".to_string());
+    }
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(format!("Import batch has {} rows; the limit is {}", rows.len(), MAX_IMPORT_ROWS));
+    }
+    Ok(rows)
+}
+
+impl IncidentTrackerContractState {
+    // Writes a validated row as a new incident. The history is replayed in
+    // time order so the hash chain reads like it was recorded live, followed
+    // by an "imported" entry stamped with the import time.
+    pub(crate) fn import_incident(&mut self, row: ValidImport, imported_by: &str, timestamp: &str) -> Result<String, String> {
+        let key = external_ref_key(&row.source, &row.external_id);
+        if let Some(existing) = self.external_refs.get(&key) {
+            return Err(format!("{} from {} was already imported as {}", row.external_id, row.source, existing));
+        }
+
+        let incident_id = self.next_incident_id(&row.created_at)?;
+        let external_ref = ExternalRef {
+            source: row.source,
+            id: row.external_id,
+            original_created_at: row.original_created_at,
+            imported_at: timestamp.to_string(),
+            imported_by: imported_by.to_string(),
+        };
+        let mut incident = Incident {
+            id: incident_id.clone(),
+            description: row.description,
+            severity: row.severity,
+            service: row.service,
+            reporter: row.reporter,
+            status: IncidentStatus::Open,
+            notes: String::new(),
+            created_at: row.created_at.clone(),
+            updated_at: row.created_at.clone(),
+            status_history: Vec::new(),
+            severity_history: Vec::new(),
+            acknowledged_by: None,
+            acknowledged_at: None,
+            assignee: None,
+            assignment_history: Vec::new(),
+            link: None,
+            linked_ids: Vec::new(),
+            merged_into: None,
+            comment_count: 0,
+            attachments: Vec::new(),
+            labels: row.labels,
+            impact: Impact::default(),
+            impact_history: Vec::new(),
+            action_item_count: 0,
+            external_ref: Some(external_ref.clone()),
+        };
+
+        let created = json!({
+            "description": incident.description,
+            "severity": incident.severity,
+            "service": incident.service,
+            "reporter": incident.reporter,
+            "external_ref": external_ref
+        });
+        self.append_log(&incident_id, "created", created.to_string(), row.created_at.clone());
+        if let Some(assignee) = row.assignee {
+            let assignment = Assignment {
+                assignee,
+                previous: None,
+                actor: imported_by.to_string(),
+                reason: "imported".to_string(),
+                timestamp: row.created_at.clone(),
+            };
+            self.record_assignment(&mut incident, "assigned", assignment)?;
+        }
+
+        // Transitions and events merged by time; transitions first on ties
+        let mut transitions = row.transitions.into_iter().peekable();
+        let mut events = row.events.into_iter().peekable();
+        loop {
+            let take_transition = match (transitions.peek(), events.peek()) {
+                (Some(t), Some(e)) => t.timestamp <= e.timestamp,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break,
+            };
+            if take_transition {
+                let Some(t) = transitions.next() else { break };
+                self.transition(&mut incident, t.to, t.actor, t.notes, t.timestamp)?;
+            } else {
+                let Some(e) = events.next() else { break };
+                let text = action_text(&e.details);
+                let seq = self.append_log(&incident_id, &e.event, e.details, e.timestamp);
+                self.index_document(DocRef::new(&incident_id, Field::Action, seq), &text);
+            }
+        }
+
+        incident.notes = row.resolution_notes;
+        self.index_document(DocRef::new(&incident_id, Field::Description, ""), &incident.description);
+        self.index_document(DocRef::new(&incident_id, Field::Notes, ""), &incident.notes);
+        self.append_log(&incident_id, "imported", serde_json::to_string(&external_ref).map_err(|e| e.to_string())?, timestamp.to_string());
+
+        self.incidents.insert(incident_id.clone(), incident);
+        self.incident_ids.push(incident_id.clone());
+        self.external_refs.insert(key, incident_id.clone());
+        Ok(incident_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_over_the_label_limit_are_rejected() {
+        let labels: Vec<String> = (0..=MAX_LABELS).map(|i| format!("k{}=v", i)).collect();
+        let row = ImportRow {
+            external_id: "OLD-1".to_string(),
+            description: "disk full".to_string(),
+            severity: "P2".to_string(),
+            service: "db".to_string(),
+            reporter: "alice".to_string(),
+            created_at: "2025-03-01T10:00:00Z".to_string(),
+            labels: labels.join(","),
+            ..ImportRow::default()
+        };
+        let result = row.validate(1_767_607_200).map(|_| ());
+        assert_eq!(result, Err(format!("{} labels given; the limit is {}", MAX_LABELS + 1, MAX_LABELS)));
+    }
+
+    #[test]
+    fn quoted_fields_keep_commas_and_doubled_quotes() {
+        let result = parse_csv("id,notes\nINC-1,\"restarted db, then \"\"cache\"\"\"\n");
+        assert_eq!(
+            result,
+            Ok(vec![
+                vec!["id".to_string(), "notes".to_string()],
+                vec!["INC-1".to_string(), "restarted db, then \"cache\"".to_string()],
+            ])
+        );
+    }
+
+    #[test]
+    fn quoted_fields_may_span_lines() {
+        let result = parse_csv("notes\r\n\"line one\r\nline two\"\r\n");
+        assert_eq!(result, Ok(vec![vec!["notes".to_string()], vec!["line one\r\nline two".to_string()]]));
+    }
+
+    #[test]
+    fn unterminated_quotes_fail_the_batch() {
+        assert!(parse_csv("id,notes\nINC-1,\"never closed\n").is_err());
+    }
+
+    #[test]
+    fn blank_lines_are_kept_except_after_the_final_line_break() {
+        let result = parse_csv("id\nINC-1\n\nINC-2\n");
+        assert_eq!(
+            result,
+            Ok(vec![
+                vec!["id".to_string()],
+                vec!["INC-1".to_string()],
+                vec![String::new()],
+                vec!["INC-2".to_string()],
+            ])
+        );
+    }
+
+    #[test]
+    fn blank_rows_are_reported_in_place() {
+        let rows = csv_rows("external_id,description\nOLD-1,disk full\n\nOLD-2,dns\n").unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1].as_ref().unwrap_err(), "Blank row");
+        assert_eq!(rows[2].as_ref().unwrap().external_id, "OLD-2");
+    }
+}
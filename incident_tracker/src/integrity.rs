@@ -0,0 +1,217 @@
+use crate::TimelineEntry;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+// prev_hash of the first entry in every incident's chain
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Everything an entry commits to, serialized in a fixed field order
+#[derive(Serialize)]
+struct HashedFields<'a> {
+    incident_id: &'a str,
+    seq: u64,
+    event: &'a str,
+    details: &'a str,
+    timestamp: &'a str,
+    merged_from: Option<&'a str>,
+    prev_hash: &'a str,
+}
+
+pub fn entry_hash(entry: &TimelineEntry) -> String {
+    let fields = HashedFields {
+        incident_id: &entry.incident_id,
+        seq: entry.seq,
+        event: &entry.event,
+        details: &entry.details,
+        timestamp: &entry.timestamp,
+        merged_from: entry.merged_from.as_deref(),
+        prev_hash: &entry.prev_hash,
+    };
+    sha256_hex(serde_json::to_string(&fields).unwrap_or_default().as_bytes())
+}
+
+// Prefix bytes that keep leaf and interior hashes in separate domains, so no
+// interior node can be passed off as a leaf (RFC 6962 style)
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+// How get_merkle_root builds the tree, for external verifiers
+pub const MERKLE_SCHEME: &str =
+    "leaf = sha256(0x00 || leaf_hex), node = sha256(0x01 || left_hex || right_hex), odd node carried up unchanged";
+
+fn prefixed_hash(prefix: u8, parts: &[&str]) -> String {
+    let mut data = vec![prefix];
+    for part in parts {
+        data.extend_from_slice(part.as_bytes());
+    }
+    sha256_hex(&data)
+}
+
+// Merkle root over entry hashes in write order, per MERKLE_SCHEME. An odd
+// node out moves up a level as it is rather than being paired with itself,
+// so [a, b, c] and [a, b, c, c] have different roots.
+pub fn merkle_root(hashes: &[String]) -> Option<String> {
+    let mut level: Vec<String> = hashes.iter().map(|hash| prefixed_hash(LEAF_PREFIX, &[hash])).collect();
+    if level.is_empty() {
+        return None;
+    }
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => prefixed_hash(NODE_PREFIX, &[left, right]),
+                _ => pair[0].clone(),
+            })
+            .collect();
+    }
+    level.pop()
+}
+
+#[derive(Debug, Serialize)]
+pub struct BrokenLink {
+    pub seq: u64,
+    pub reason: String,
+}
+
+// Walks the chain in write order; `entries[i]` is None when seq i is missing
+pub fn verify_chain(incident_id: &str, entries: &[Option<TimelineEntry>]) -> Option<BrokenLink> {
+    let mut expected_prev = GENESIS_HASH.to_string();
+
+    for (seq, entry) in entries.iter().enumerate() {
+        let seq = seq as u64;
+        let broken = |reason: &str| Some(BrokenLink { seq, reason: reason.to_string() });
+
+        let entry = match entry {
+            Some(entry) => entry,
+            None => return broken("entry is missing"),
+        };
+        if entry.seq != seq || entry.incident_id != incident_id {
+            return broken("entry is stored under the wrong key");
+        }
+        if entry.prev_hash != expected_prev {
+            return broken("prev_hash does not match the previous entry");
+        }
+        if entry_hash(entry) != entry.hash {
+            return broken("content does not match its hash");
+        }
+        expected_prev = entry.hash.clone();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves() -> Vec<String> {
+        ["a", "b", "c"].iter().map(|leaf| sha256_hex(leaf.as_bytes())).collect()
+    }
+
+    // A valid chain of `len` entries for INC-1
+    fn chain(len: u64) -> Vec<Option<TimelineEntry>> {
+        let mut prev_hash = GENESIS_HASH.to_string();
+        (0..len)
+            .map(|seq| {
+                let mut entry = TimelineEntry {
+                    seq,
+                    incident_id: "INC-1".to_string(),
+                    event: "comment".to_string(),
+                    details: format!("{{\"text\":\"update {}\"}}", seq),
+                    timestamp: format!("2026-01-05T10:0{}:00Z", seq),
+                    merged_from: None,
+                    prev_hash: prev_hash.clone(),
+                    hash: String::new(),
+                    write_seq: seq,
+                };
+                entry.hash = entry_hash(&entry);
+                prev_hash = entry.hash.clone();
+                Some(entry)
+            })
+            .collect()
+    }
+
+    fn broken_at(entries: &[Option<TimelineEntry>]) -> Option<(u64, String)> {
+        verify_chain("INC-1", entries).map(|broken| (broken.seq, broken.reason))
+    }
+
+    #[test]
+    fn merkle_roots_are_pinned() {
+        let leaves = leaves();
+        assert_eq!(merkle_root(&[]), None);
+        assert_eq!(
+            merkle_root(&leaves[..1]).as_deref(),
+            Some("1ba678c6e6499afa28c1ef1483d582524fc0516a0a455eea17b47434b487d184")
+        );
+        assert_eq!(
+            merkle_root(&leaves[..2]).as_deref(),
+            Some("45bbdd21e1f5c76f41784f8c1af17d6a989cf01350b25d651fe5f2344140999e")
+        );
+        assert_eq!(
+            merkle_root(&leaves).as_deref(),
+            Some("eb99307b943b28636c65c2bce1968cd269e42da63dd772fe71f11fb022b75eb9")
+        );
+    }
+
+    #[test]
+    fn a_single_leaf_is_still_hashed() {
+        let leaves = leaves();
+        assert_ne!(merkle_root(&leaves[..1]), Some(leaves[0].clone()));
+    }
+
+    #[test]
+    fn duplicating_the_odd_leaf_changes_the_root() {
+        let mut padded = leaves();
+        padded.push(padded[2].clone());
+        assert_ne!(merkle_root(&leaves()), merkle_root(&padded));
+    }
+
+    #[test]
+    fn write_seq_is_not_hashed() {
+        let mut entry = chain(1).remove(0).unwrap();
+        let hash = entry_hash(&entry);
+        entry.write_seq = 42;
+        assert_eq!(entry_hash(&entry), hash);
+        entry.timestamp = "2026-01-05T11:00:00Z".to_string();
+        assert_ne!(entry_hash(&entry), hash);
+    }
+
+    #[test]
+    fn an_intact_chain_verifies() {
+        assert_eq!(broken_at(&chain(3)), None);
+    }
+
+    #[test]
+    fn a_modified_entry_is_reported() {
+        let mut entries = chain(3);
+        if let Some(entry) = entries[1].as_mut() {
+            entry.details = "{\"text\":\"rewritten\"}".to_string();
+        }
+        assert_eq!(broken_at(&entries), Some((1, "content does not match its hash".to_string())));
+    }
+
+    #[test]
+    fn a_missing_entry_is_reported() {
+        let mut entries = chain(3);
+        entries[1] = None;
+        assert_eq!(broken_at(&entries), Some((1, "entry is missing".to_string())));
+    }
+
+    #[test]
+    fn a_wrong_prev_hash_is_reported() {
+        let mut entries = chain(3);
+        if let Some(entry) = entries[2].as_mut() {
+            // rehashed, so only the link to the previous entry is wrong
+            entry.prev_hash = GENESIS_HASH.to_string();
+            entry.hash = entry_hash(entry);
+        }
+        assert_eq!(
+            broken_at(&entries),
+            Some((2, "prev_hash does not match the previous entry".to_string()))
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+pub type Labels = BTreeMap<String, String>;
+
+pub const MAX_LABELS: usize = 32;
+const MAX_LABEL_LEN: usize = 63;
+
+// Keys are case-insensitive and stored lowercase; values keep their case.
+// Neither may contain the ',', '=' or '!' used by selectors.
+fn valid_label_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | ':')
+}
+
+pub fn normalize_key(key: &str) -> Result<String, String> {
+    let key = key.trim().to_lowercase();
+    if key.is_empty() || key.len() > MAX_LABEL_LEN || !key.chars().all(valid_label_char) {
+        return Err(format!(
+            "Invalid label key '{}' (expected 1-{} characters from a-z, 0-9, '-', '_', '.', '/', ':')",
+            key, MAX_LABEL_LEN
+        ));
+    }
+    Ok(key)
+}
+
+fn normalize_value(key: &str, value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() || value.len() > MAX_LABEL_LEN || !value.chars().all(valid_label_char) {
+        return Err(format!(
+            "Invalid value '{}' for label '{}' (expected 1-{} characters from A-Z, a-z, 0-9, '-', '_', '.', '/', ':')",
+            value, key, MAX_LABEL_LEN
+        ));
+    }
+    Ok(value.to_string())
+}
+
+// Parses "region=eu-west,tier=free" into labels, rejecting duplicate keys
+pub fn parse_labels(value: &str) -> Result<Labels, String> {
+    let mut labels = Labels::new();
+    for pair in value.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid label '{}' (expected key=value)", pair))?;
+        let key = normalize_key(key)?;
+        let value = normalize_value(&key, value)?;
+        if labels.insert(key.clone(), value).is_some() {
+            return Err(format!("Label '{}' is given more than once", key));
+        }
+    }
+    if labels.is_empty() {
+        return Err("No labels given (expected key=value pairs)".to_string());
+    }
+    Ok(labels)
+}
+
+pub fn parse_keys(value: &str) -> Result<Vec<String>, String> {
+    let keys: Vec<String> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(normalize_key)
+        .collect::<Result<_, _>>()?;
+    if keys.is_empty() {
+        return Err("No label keys given".to_string());
+    }
+    Ok(keys)
+}
+
+#[derive(Debug)]
+enum Requirement {
+    Equals(String, String),
+    NotEquals(String, String),
+    Exists(String),
+    NotExists(String),
+}
+
+// A comma-separated list of requirements that must all hold:
+// "key=value", "key!=value", "key" (present) and "!key" (absent).
+// Values compare case-insensitively; "key!=value" also matches incidents without the key.
+#[derive(Debug, Default)]
+pub struct LabelSelector {
+    requirements: Vec<Requirement>,
+}
+
+impl LabelSelector {
+    pub fn parse(selector: &str) -> Result<Self, String> {
+        let requirements = selector
+            .split(',')
+            .map(str::trim)
+            .filter(|term| !term.is_empty())
+            .map(|term| {
+                if let Some((key, value)) = term.split_once("!=") {
+                    let key = normalize_key(key)?;
+                    let value = normalize_value(&key, value)?;
+                    Ok(Requirement::NotEquals(key, value))
+                } else if let Some((key, value)) = term.split_once('=') {
+                    let key = normalize_key(key)?;
+                    let value = normalize_value(&key, value)?;
+                    Ok(Requirement::Equals(key, value))
+                } else if let Some(key) = term.strip_prefix('!') {
+                    Ok(Requirement::NotExists(normalize_key(key)?))
+                } else {
+                    Ok(Requirement::Exists(normalize_key(term)?))
+                }
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(LabelSelector { requirements })
+    }
+
+    pub fn matches(&self, labels: &Labels) -> bool {
+        self.requirements.iter().all(|requirement| match requirement {
+            Requirement::Equals(key, value) => labels.get(key).is_some_and(|v| v.eq_ignore_ascii_case(value)),
+            Requirement::NotEquals(key, value) => labels.get(key).is_none_or(|v| !v.eq_ignore_ascii_case(value)),
+            Requirement::Exists(key) => labels.contains_key(key),
+            Requirement::NotExists(key) => !labels.contains_key(key),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(value: &str) -> Labels {
+        parse_labels(value).unwrap()
+    }
+
+    #[test]
+    fn not_exists_matches_only_without_the_key() {
+        let selector = LabelSelector::parse("!customer").unwrap();
+        assert!(selector.matches(&labels("region=eu-west")));
+        assert!(!selector.matches(&labels("region=eu-west,customer=acme")));
+    }
+
+    #[test]
+    fn not_exists_keys_are_case_insensitive() {
+        let selector = LabelSelector::parse(" !Customer ").unwrap();
+        assert!(!selector.matches(&labels("customer=acme")));
+    }
+
+    #[test]
+    fn not_equals_matches_other_values_and_missing_keys() {
+        let selector = LabelSelector::parse("tier!=free").unwrap();
+        assert!(selector.matches(&labels("tier=enterprise")));
+        assert!(selector.matches(&labels("region=eu-west")));
+        assert!(!selector.matches(&labels("tier=FREE")));
+    }
+
+    #[test]
+    fn not_equals_requires_a_value() {
+        assert!(LabelSelector::parse("tier!=").is_err());
+        assert!(LabelSelector::parse("!=free").is_err());
+    }
+
+    #[test]
+    fn negations_combine_with_other_requirements() {
+        let selector = LabelSelector::parse("region=eu-west,!customer,tier!=free").unwrap();
+        assert!(selector.matches(&labels("region=EU-West,tier=pro")));
+        assert!(!selector.matches(&labels("region=eu-west,tier=free")));
+        assert!(!selector.matches(&labels("region=eu-west,customer=acme")));
+    }
+
+    #[test]
+    fn negation_is_only_a_key_prefix() {
+        assert!(LabelSelector::parse("!tier=free").is_err());
+    }
+}
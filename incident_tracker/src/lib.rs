@@ -1,8 +1,54 @@
+// MCP tool signatures mirror the flat parameter lists in incident_tracker.widl
+#![allow(clippy::too_many_arguments)]
+
 use serde::{Deserialize, Serialize};
-use weil_macros::{constructor, mutate, query, secured, smart_contract, WeilType};
+use weil_macros::{constructor, mutate, query, smart_contract, WeilType};
+use weil_rs::collections::{map::WeilMap, plottable::Plottable, streaming::ByteStream, vec::WeilVec, WeilId};
 use weil_rs::config::Secrets;
+use weil_rs::runtime::Runtime;
 use weil_rs::webserver::WebServer;
 use serde_json::json;
+use incident_common::{IncidentStatus, Severity};
+
+mod access;
+mod action_items;
+mod actions;
+mod comments;
+mod evidence;
+mod export;
+mod history;
+mod impact;
+mod import;
+mod integrity;
+mod labels;
+mod links;
+mod metrics;
+mod postmortem;
+mod query;
+mod search;
+mod similar;
+mod time;
+mod trends;
+
+use access::{AccessDenial, Role};
+use action_items::{action_item_key, parse_due_date, parse_owner, ActionItem, ActionItemUpdate, ItemStatus};
+use actions::Action;
+use comments::{comment_key, CommentRevision, IncidentComment, Visibility};
+use export::Dataset;
+use evidence::{content_hash, decode_content, Attachment, MAX_INCIDENT_EVIDENCE_BYTES};
+use history::FieldChange;
+use impact::{Impact, ImpactUpdate};
+use import::{parse_batch, ExternalRef, RowResult};
+use integrity::{entry_hash, merkle_root, verify_chain, GENESIS_HASH, MERKLE_SCHEME};
+use labels::{parse_keys, parse_labels, Labels, MAX_LABELS};
+use links::{IncidentLink, LinkKind};
+use metrics::response_metrics;
+use postmortem::PostMortem;
+use query::{paginate, IncidentFilter};
+use search::{action_text, DocRef, Field};
+use similar::MAX_SIMILAR_INCIDENTS;
+use time::{format_timestamp, normalize_timestamp, parse_block_time, parse_timestamp};
+use trends::{Sample, Series};
 
 trait IncidentTracker {
     fn new() -> Result<Self, String>
@@ -10,15 +56,401 @@ trait IncidentTracker {
         Self: Sized;
     async fn create_incident(&mut self, description: String, severity: String, service: String, reporter: String, timestamp: String) -> Result<String, String>;
     async fn log_action(&mut self, incident_id: String, action_type: String, details: String, timestamp: String) -> Result<String, String>;
-    async fn update_status(&mut self, incident_id: String, status: String, notes: String) -> Result<String, String>;
+    async fn update_status(&mut self, incident_id: String, status: String, notes: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn change_severity(&mut self, incident_id: String, severity: String, reason: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn acknowledge_incident(&mut self, incident_id: String, responder: String, timestamp: String) -> Result<String, String>;
+    async fn assign_incident(&mut self, incident_id: String, assignee: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn reassign_incident(&mut self, incident_id: String, assignee: String, actor: String, reason: String, timestamp: String) -> Result<String, String>;
+    async fn link_incidents(&mut self, incident_id: String, target_id: String, relation: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn unlink_incident(&mut self, incident_id: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn merge_incidents(&mut self, source_id: String, survivor_id: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn import_incidents(&mut self, batch: String, format: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn grant_role(&mut self, identity: String, role: String, timestamp: String) -> Result<String, String>;
+    async fn revoke_role(&mut self, identity: String, timestamp: String) -> Result<String, String>;
+    async fn get_role(&self, identity: String) -> Result<String, String>;
+    async fn get_access_denials(&self, limit: u32) -> Result<String, String>;
+    async fn add_comment(&mut self, incident_id: String, author: String, text: String, visibility: String, timestamp: String) -> Result<String, String>;
+    async fn edit_comment(&mut self, incident_id: String, comment_id: String, text: String, editor: String, timestamp: String) -> Result<String, String>;
+    async fn list_comments(&self, incident_id: String, visibility: String) -> Result<String, String>;
+    async fn attach_evidence(&mut self, incident_id: String, name: String, content_type: String, content: String, encoding: String, uploader: String, timestamp: String) -> Result<String, String>;
+    async fn list_evidence(&self, incident_id: String) -> Result<String, String>;
+    async fn get_evidence(&self, hash: String) -> Result<ByteStream, String>;
+    async fn add_action_item(&mut self, incident_id: String, title: String, owner: String, due_date: String, priority: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn update_action_item(&mut self, incident_id: String, item_id: String, owner: String, due_date: String, priority: String, status: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn list_action_items(&self, incident_id: String, status: String) -> Result<String, String>;
+    async fn get_overdue_action_items(&self, as_of: String) -> Result<String, String>;
+    async fn update_impact(&mut self, incident_id: String, started_at: String, ended_at: String, regions: String, affected_users: String, components: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn add_labels(&mut self, incident_id: String, labels: String, actor: String, timestamp: String) -> Result<String, String>;
+    async fn remove_labels(&mut self, incident_id: String, keys: String, actor: String, timestamp: String) -> Result<String, String>;
     async fn get_incident_timeline(&self, incident_id: String) -> Result<String, String>;
+    async fn verify_incident_integrity(&self, incident_id: String) -> Result<String, String>;
+    async fn get_merkle_root(&self, incident_id: String) -> Result<String, String>;
+    async fn generate_postmortem(&self, incident_id: String, format: String) -> Result<String, String>;
+    async fn search_incidents(&self, query: String, limit: u32) -> Result<String, String>;
     async fn get_active_incidents(&self) -> Result<String, String>;
+    async fn query_incidents(&self, severity: String, service: String, status: String, reporter: String, created_from: String, created_to: String, labels: String, cursor: String, limit: u32) -> Result<String, String>;
+    async fn export_incident_data(&self, dataset: String, format: String, from: String, to: String, cursor: String, limit: u32) -> Result<String, String>;
+    async fn get_export_schemas(&self) -> Result<String, String>;
+    async fn get_response_metrics(&self, from: String, to: String) -> Result<String, String>;
+    async fn plot_incidents_per_day(&self, from: String, to: String) -> Result<Plottable, String>;
+    async fn plot_open_incidents(&self, from: String, to: String) -> Result<Plottable, String>;
+    async fn plot_mttr_per_week(&self, from: String, to: String) -> Result<Plottable, String>;
     fn tools(&self) -> String;
     fn prompts(&self) -> String;
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusTransition {
+    pub from: IncidentStatus,
+    pub to: IncidentStatus,
+    pub actor: String,
+    pub notes: String,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeverityChange {
+    pub from: Severity,
+    pub to: Severity,
+    pub reason: String,
+    pub actor: String,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Assignment {
+    pub assignee: String,
+    pub previous: Option<String>,
+    pub actor: String,
+    pub reason: String,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Incident {
+    pub id: String,
+    pub description: String,
+    pub severity: Severity,
+    pub service: String,
+    pub reporter: String,
+    pub status: IncidentStatus,
+    // notes from the latest resolution; notes on other transitions live in status_history
+    pub notes: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub status_history: Vec<StatusTransition>,
+    pub severity_history: Vec<SeverityChange>,
+    pub acknowledged_by: Option<String>,
+    pub acknowledged_at: Option<String>,
+    pub assignee: Option<String>,
+    pub assignment_history: Vec<Assignment>,
+    // parent or duplicate-of relation, if this incident is linked under another
+    pub link: Option<IncidentLink>,
+    // incidents linked under this one
+    pub linked_ids: Vec<String>,
+    pub merged_into: Option<String>,
+    #[serde(default)]
+    pub comment_count: u64,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    // free-form key/value labels such as region or customer tier
+    #[serde(default)]
+    pub labels: Labels,
+    #[serde(default)]
+    pub impact: Impact,
+    #[serde(default)]
+    pub impact_history: Vec<FieldChange>,
+    #[serde(default)]
+    pub action_item_count: u64,
+    // set on incidents brought in by import_incidents
+    #[serde(default)]
+    pub external_ref: Option<ExternalRef>,
+}
+
+impl Incident {
+    fn is_active(&self) -> bool {
+        self.status.is_active() && self.merged_into.is_none()
+    }
+
+    fn ensure_not_merged(&self) -> Result<(), String> {
+        match &self.merged_into {
+            Some(survivor) => Err(format!("Incident {} was merged into {}", self.id, survivor)),
+            None => Ok(()),
+        }
+    }
+
+    fn ensure_active(&self) -> Result<(), String> {
+        self.ensure_not_merged()?;
+        if self.is_active() {
+            Ok(())
+        } else {
+            Err(format!("Incident {} is {}; reopen it first", self.id, self.status))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub seq: u64,
+    pub incident_id: String,
+    pub event: String,
+    pub details: String,
+    pub timestamp: String,
+    // set on entries copied over from an incident merged into this one
+    #[serde(default)]
+    pub merged_from: Option<String>,
+    // hash of the previous entry in this incident's log, chaining the entries
+    #[serde(default)]
+    pub prev_hash: String,
+    // hash of this entry's content including prev_hash
+    #[serde(default)]
+    pub hash: String,
+    // position among all log writes across incidents; not part of the hash
+    pub write_seq: u64,
+}
+
+// How far ahead of block time a caller-supplied timestamp may run, to allow for clock skew
+const MAX_CLOCK_SKEW_SECONDS: i64 = 300;
+
+fn log_key(incident_id: &str, seq: u64) -> String {
+    format!("incident:{}:log:{:06}", incident_id, seq)
+}
+
 #[derive(Serialize, Deserialize, WeilType)]
-pub struct IncidentTrackerContractState {}
+pub struct IncidentTrackerContractState {
+    // incident records keyed by incident ID
+    incidents: WeilMap<String, Incident>,
+    // incident IDs in creation order, since WeilMap cannot be iterated
+    incident_ids: WeilVec<String>,
+    // append-only event log keyed by log_key(incident_id, seq)
+    timeline: WeilMap<String, TimelineEntry>,
+    // number of log entries written per incident
+    timeline_len: WeilMap<String, u64>,
+    // last issued incident sequence number, never reused
+    incident_seq: u64,
+    // number of log entries written across all incidents
+    log_writes: u64,
+    // role held by each caller identity
+    roles: WeilMap<String, Role>,
+    // number of identities holding the admin role, never allowed to reach zero
+    admin_count: u32,
+    // calls rejected for lacking a role
+    access_denials: WeilVec<AccessDenial>,
+    // responder comments keyed by comment_key(incident_id, comment_id)
+    comments: WeilMap<String, IncidentComment>,
+    // evidence blobs keyed by the sha256 of their content
+    evidence: WeilMap<String, Vec<u8>>,
+    // full-text index: pages of the documents each search term appears in,
+    // keyed by posting_key(term, page)
+    search_index: WeilMap<String, Vec<DocRef>>,
+    // follow-up work keyed by action_item_key(incident_id, item_id)
+    action_items: WeilMap<String, ActionItem>,
+    // imported incidents keyed by external_ref_key(source, external_id)
+    external_refs: WeilMap<String, String>,
+    // number of posting pages written per search term
+    search_pages: WeilMap<String, u64>,
+}
+
+// x values are Unix seconds at the start of each bucket
+fn time_series_plot(title: &str, y_axis: &str, series: Vec<(String, Series)>) -> Plottable {
+    series.into_iter().fold(
+        Plottable::new_with_time_series()
+            .label(title)
+            .x_axis_label("time")
+            .y_axis_label(y_axis),
+        |plot, (name, points)| {
+            plot.add_series(name, points.into_iter().map(|(x, y)| (x as f64, y)).collect())
+        },
+    )
+}
+
+impl IncidentTrackerContractState {
+    fn load_incident(&self, incident_id: &str) -> Result<Incident, String> {
+        self.incidents
+            .get(&incident_id.to_string())
+            .ok_or_else(|| format!("Incident {} not found", incident_id))
+    }
+
+    // Resolves the timestamp a mutation records: block time when the caller
+    // omits one, otherwise the caller's time normalized to UTC, provided it
+    // is not in the future
+    fn event_time(&self, timestamp: &str) -> Result<String, String> {
+        let now = parse_block_time(&Runtime::block_timestamp())?;
+        if timestamp.trim().is_empty() {
+            return Ok(format_timestamp(now));
+        }
+        
+        let at = parse_timestamp(timestamp)?;
+        if at > now + MAX_CLOCK_SKEW_SECONDS {
+            return Err(format!(
+                "Timestamp {} is in the future (block time is {})",
+                timestamp.trim(),
+                format_timestamp(now)
+            ));
+        }
+        Ok(format_timestamp(at))
+    }
+
+    // Loads an incident that is about to be written to, rejecting a timestamp
+    // earlier than the last event on it or on the incidents linked under it
+    fn load_for_event(&self, incident_id: &str, timestamp: &str) -> Result<Incident, String> {
+        let incident = self.load_incident(incident_id)?;
+        let at = parse_timestamp(timestamp)?;
+        
+        for id in std::iter::once(&incident.id).chain(&incident.linked_ids) {
+            let len = self.timeline_len.get(id).unwrap_or(0);
+            let Some(last) = len.checked_sub(1).and_then(|seq| self.timeline.get(&log_key(id, seq))) else {
+                continue;
+            };
+            if parse_timestamp(&last.timestamp).is_ok_and(|last_at| at < last_at) {
+                return Err(format!(
+                    "Timestamp {} is earlier than the last event on {} ({})",
+                    timestamp, id, last.timestamp
+                ));
+            }
+        }
+        Ok(incident)
+    }
+
+    fn all_incidents(&self) -> Vec<Incident> {
+        (0..self.incident_ids.len())
+            .filter_map(|i| self.incident_ids.get(i))
+            .filter_map(|id| self.incidents.get(&id))
+            .collect()
+    }
+
+    // Issues the next ID from the contract-owned sequence, e.g. INC-2026-000123.
+    // The year is taken from the report timestamp purely for readability;
+    // uniqueness comes from the sequence alone.
+    fn next_incident_id(&mut self, timestamp: &str) -> Result<String, String> {
+        let year = timestamp
+            .get(..4)
+            .filter(|year| year.chars().all(|c| c.is_ascii_digit()))
+            .ok_or_else(|| format!("Invalid timestamp '{}': expected it to start with a year", timestamp))?;
+
+        self.incident_seq += 1;
+        Ok(format!("INC-{}-{:06}", year, self.incident_seq))
+    }
+
+    fn trend_window(&self, from: &str, to: &str) -> Result<(Vec<Sample>, i64, i64), String> {
+        let samples = trends::samples(&self.all_incidents());
+        let (start, end) = trends::window(&samples, from, to)?
+            .ok_or_else(|| "No incident data in the requested window".to_string())?;
+        Ok((samples, start, end))
+    }
+
+    // Moves an incident to a new status and records the transition in both
+    // the incident's status history and the timeline. The caller saves the incident.
+    fn transition(
+        &mut self,
+        incident: &mut Incident,
+        to: IncidentStatus,
+        actor: String,
+        notes: String,
+        timestamp: String,
+    ) -> Result<(), String> {
+        let from = incident.status;
+        if !from.can_transition_to(to) {
+            let allowed: Vec<&str> = from.next().iter().map(|s| s.as_str()).collect();
+            return Err(format!(
+                "Illegal status transition for {}: {} -> {} (allowed from {}: {})",
+                incident.id, from, to, from, allowed.join(", ")
+            ));
+        }
+
+        // Leaving Open is the acknowledgement, whichever path it takes, so
+        // MTTA does not depend on acknowledge_incident having been called
+        if from == IncidentStatus::Open && to != IncidentStatus::Open && incident.acknowledged_at.is_none() {
+            incident.acknowledged_by = Some(actor.clone());
+            incident.acknowledged_at = Some(timestamp.clone());
+            self.append_log(&incident.id, "acknowledged", json!({ "responder": actor }).to_string(), timestamp.clone());
+        }
+
+        let transition = StatusTransition {
+            from,
+            to,
+            actor,
+            notes,
+            timestamp: timestamp.clone(),
+        };
+        let event = if to == IncidentStatus::Open { "reopened" } else { "status_changed" };
+        let details = serde_json::to_string(&transition).map_err(|e| e.to_string())?;
+        let notes = transition.notes.clone();
+
+        incident.status = to;
+        incident.updated_at = timestamp.clone();
+        incident.status_history.push(transition);
+        let seq = self.append_log(&incident.id, event, details, timestamp);
+        // Each change's notes stay searchable after later changes replace them
+        if !notes.trim().is_empty() {
+            self.index_document(DocRef::new(&incident.id, Field::Notes, seq), &notes);
+        }
+        Ok(())
+    }
+
+    fn record_assignment(&mut self, incident: &mut Incident, event: &str, assignment: Assignment) -> Result<(), String> {
+        let details = serde_json::to_string(&assignment).map_err(|e| e.to_string())?;
+        incident.assignee = Some(assignment.assignee.clone());
+        incident.updated_at = assignment.timestamp.clone();
+        self.append_log(&incident.id, event, details, assignment.timestamp.clone());
+        incident.assignment_history.push(assignment);
+        Ok(())
+    }
+
+    // The only writer of the timeline: entries always land on a fresh key and
+    // there is deliberately no way to edit or remove them afterwards.
+    fn append_entry(&mut self, incident_id: &str, mut entry: TimelineEntry) -> u64 {
+        let seq = self.timeline_len.get(&incident_id.to_string()).unwrap_or(0);
+        let prev_hash = match seq {
+            0 => None,
+            _ => self.timeline.get(&log_key(incident_id, seq - 1)).map(|prev| prev.hash),
+        };
+        entry.seq = seq;
+        entry.incident_id = incident_id.to_string();
+        entry.prev_hash = prev_hash.unwrap_or_else(|| GENESIS_HASH.to_string());
+        entry.hash = entry_hash(&entry);
+        entry.write_seq = self.log_writes;
+
+        self.log_writes += 1;
+        self.timeline.insert(log_key(incident_id, seq), entry);
+        self.timeline_len.insert(incident_id.to_string(), seq + 1);
+        seq
+    }
+
+    fn append_log(&mut self, incident_id: &str, event: &str, details: String, timestamp: String) -> u64 {
+        let entry = TimelineEntry {
+            seq: 0,
+            incident_id: incident_id.to_string(),
+            event: event.to_string(),
+            details,
+            timestamp,
+            merged_from: None,
+            prev_hash: String::new(),
+            hash: String::new(),
+            write_seq: 0,
+        };
+        self.append_entry(incident_id, entry)
+    }
+
+    // Log entries in write order, with None for any seq missing from storage
+    fn load_chain(&self, incident_id: &str) -> Vec<Option<TimelineEntry>> {
+        let len = self.timeline_len.get(&incident_id.to_string()).unwrap_or(0);
+        (0..len)
+            .map(|seq| self.timeline.get(&log_key(incident_id, seq)))
+            .collect()
+    }
+
+    // Log entries sorted chronologically, falling back to write order for ties
+    fn load_timeline(&self, incident_id: &str) -> Vec<TimelineEntry> {
+        let len = self.timeline_len.get(&incident_id.to_string()).unwrap_or(0);
+        let mut entries: Vec<TimelineEntry> = (0..len)
+            .filter_map(|seq| self.timeline.get(&log_key(incident_id, seq)))
+            .collect();
+
+        entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        entries
+    }
+}
 
 #[smart_contract]
 impl IncidentTracker for IncidentTrackerContractState {
@@ -27,7 +459,27 @@ impl IncidentTracker for IncidentTrackerContractState {
     where
         Self: Sized,
     {
-        Ok(IncidentTrackerContractState {})
+        // The deploying identity becomes the first admin
+        let mut roles = WeilMap::new(WeilId(4));
+        roles.insert(Runtime::sender(), Role::Admin);
+        
+        Ok(IncidentTrackerContractState {
+            incidents: WeilMap::new(WeilId(0)),
+            incident_ids: WeilVec::new(WeilId(1)),
+            timeline: WeilMap::new(WeilId(2)),
+            timeline_len: WeilMap::new(WeilId(3)),
+            incident_seq: 0,
+            log_writes: 0,
+            roles,
+            admin_count: 1,
+            access_denials: WeilVec::new(WeilId(5)),
+            comments: WeilMap::new(WeilId(6)),
+            evidence: WeilMap::new(WeilId(7)),
+            search_index: WeilMap::new(WeilId(8)),
+            action_items: WeilMap::new(WeilId(9)),
+            external_refs: WeilMap::new(WeilId(10)),
+            search_pages: WeilMap::new(WeilId(11)),
+        })
     }
 
     #[mutate]
@@ -39,23 +491,63 @@ impl IncidentTracker for IncidentTrackerContractState {
         reporter: String,
         timestamp: String,
     ) -> Result<String, String> {
-        let incident_id = format!(
-            "INC-{}", 
-            timestamp.replace(":", "").replace("-", "").replace("T", "").replace("Z", "")
-        );
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("create_incident", Role::Responder, &timestamp)?;
+        let severity: Severity = severity.parse()?;
+        let incident_id = self.next_incident_id(&timestamp)?;
+        
+        if self.incidents.get(&incident_id).is_some() {
+            return Err(format!("Incident {} already exists", incident_id));
+        }
         
-        let incident_data = json!({
-            "id": incident_id,
+        let created = json!({
             "description": description,
             "severity": severity,
             "service": service,
-            "reporter": reporter,
-            "timestamp": timestamp,
-            "status": "open",
-            "created_at": timestamp
+            "reporter": reporter
         });
         
-        Ok(format!("{{\"incident_id\": \"{}\", \"status\": \"created\"}}", incident_id))
+        let incident = Incident {
+            id: incident_id.clone(),
+            description,
+            severity,
+            service,
+            reporter,
+            status: IncidentStatus::Open,
+            notes: String::new(),
+            created_at: timestamp.clone(),
+            updated_at: timestamp.clone(),
+            status_history: Vec::new(),
+            severity_history: Vec::new(),
+            acknowledged_by: None,
+            acknowledged_at: None,
+            assignee: None,
+            assignment_history: Vec::new(),
+            link: None,
+            linked_ids: Vec::new(),
+            merged_into: None,
+            comment_count: 0,
+            attachments: Vec::new(),
+            labels: Labels::new(),
+            impact: Impact::default(),
+            impact_history: Vec::new(),
+            action_item_count: 0,
+            external_ref: None,
+        };
+        
+        // Past incidents that look like this one, with what fixed them
+        let similar = self.similar_incidents(&incident, MAX_SIMILAR_INCIDENTS);
+        
+        self.index_document(DocRef::new(&incident_id, Field::Description, ""), &incident.description);
+        self.incidents.insert(incident_id.clone(), incident);
+        self.incident_ids.push(incident_id.clone());
+        self.append_log(&incident_id, "created", created.to_string(), timestamp);
+        
+        Ok(json!({
+            "incident_id": incident_id,
+            "status": "created",
+            "similar_incidents": similar
+        }).to_string())
     }
 
     #[mutate]
@@ -66,102 +558,1832 @@ impl IncidentTracker for IncidentTrackerContractState {
         details: String,
         timestamp: String,
     ) -> Result<String, String> {
-        let log_key = format!(
-            "incident:{}:log:{}", 
-            incident_id, 
-            timestamp.replace(":", "").replace("-", "").replace("T", "").replace("Z", "")
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("log_action", Role::Responder, &timestamp)?;
+        let action_type = action_type.trim().to_lowercase();
+        let details = Action::parse(&action_type, &details)?.to_details();
+        let incident = self.load_for_event(&incident_id, &timestamp)?;
+        incident.ensure_not_merged()?;
+        self.propagate_action(&incident, &action_type, &details, &timestamp);
+        let text = action_text(&details);
+        let seq = self.append_log(&incident_id, &action_type, details, timestamp);
+        self.index_document(DocRef::new(&incident_id, Field::Action, seq), &text);
+        
+        Ok(format!(
+            "{{\"status\": \"logged\", \"action_type\": \"{}\", \"seq\": {}, \"propagated_to\": {}}}",
+            action_type, seq, incident.linked_ids.len()
+        ))
+    }
+
+    #[mutate]
+    async fn update_status(
+        &mut self,
+        incident_id: String,
+        status: String,
+        notes: String,
+        actor: String,
+        timestamp: String,
+    ) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        let to: IncidentStatus = status.parse()?;
+        // Closing signs off on an incident, so it is reserved for commanders
+        let required = if to == IncidentStatus::Closed { Role::IncidentCommander } else { Role::Responder };
+        self.authorize("update_status", required, &timestamp)?;
+        
+        let mut incident = self.load_for_event(&incident_id, &timestamp)?;
+        incident.ensure_not_merged()?;
+        let from = incident.status;
+        
+        if to == IncidentStatus::Closed {
+            let unassigned = self.unassigned_p0_items(&incident_id, incident.action_item_count);
+            if !unassigned.is_empty() {
+                return Err(format!(
+                    "Incident {} cannot be closed while P0 action items are unassigned: {}",
+                    incident_id,
+                    unassigned.join(", ")
+                ));
+            }
+        }
+        
+        self.transition(&mut incident, to, actor.clone(), notes.clone(), timestamp.clone())?;
+        // Closing or reopening must not wipe out what fixed the incident
+        if to == IncidentStatus::Resolved && !notes.trim().is_empty() {
+            incident.notes = notes;
+        }
+        let (propagated, skipped) = self.propagate_status(&incident, to, &actor, &timestamp)?;
+        self.incidents.insert(incident_id.clone(), incident);
+        
+        Ok(json!({
+            "incident_id": incident_id,
+            "old_status": from,
+            "new_status": to,
+            "propagated_to": propagated,
+            "not_propagated": skipped
+        }).to_string())
+    }
+
+    #[mutate]
+    async fn change_severity(
+        &mut self,
+        incident_id: String,
+        severity: String,
+        reason: String,
+        actor: String,
+        timestamp: String,
+    ) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("change_severity", Role::IncidentCommander, &timestamp)?;
+        let to: Severity = severity.parse()?;
+        let mut incident = self.load_for_event(&incident_id, &timestamp)?;
+        incident.ensure_not_merged()?;
+        
+        let from = incident.severity;
+        if from == to {
+            return Err(format!("Incident {} is already {}", incident_id, to));
+        }
+        if reason.trim().is_empty() {
+            return Err("A reason is required to change severity".to_string());
+        }
+        
+        // P0 sorts first, so a smaller severity is an escalation
+        let escalated = to < from;
+        let event = if escalated { "severity_escalated" } else { "severity_deescalated" };
+        let change = SeverityChange { from, to, reason, actor, timestamp: timestamp.clone() };
+        let details = serde_json::to_string(&change).map_err(|e| e.to_string())?;
+        
+        incident.severity = to;
+        incident.updated_at = timestamp.clone();
+        incident.severity_history.push(change);
+        self.incidents.insert(incident_id.clone(), incident);
+        self.append_log(&incident_id, event, details, timestamp);
+        
+        // Paging policy depends on severity, so a new P0 has to be paged again
+        Ok(json!({
+            "incident_id": incident_id,
+            "old_severity": from,
+            "new_severity": to,
+            "escalated": escalated,
+            "repage_required": to == Severity::P0
+        }).to_string())
+    }
+
+    #[mutate]
+    async fn acknowledge_incident(
+        &mut self,
+        incident_id: String,
+        responder: String,
+        timestamp: String,
+    ) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("acknowledge_incident", Role::Responder, &timestamp)?;
+        let mut incident = self.load_for_event(&incident_id, &timestamp)?;
+        incident.ensure_active()?;
+        
+        if let Some(by) = &incident.acknowledged_by {
+            return Err(format!("Incident {} was already acknowledged by {}", incident_id, by));
+        }
+        
+        incident.acknowledged_by = Some(responder.clone());
+        incident.acknowledged_at = Some(timestamp.clone());
+        self.append_log(
+            &incident_id,
+            "acknowledged",
+            json!({ "responder": responder }).to_string(),
+            timestamp.clone(),
         );
         
-        let log_data = json!({
+        // The acknowledging responder owns the incident unless someone already does
+        if incident.assignee.is_none() {
+            let assignment = Assignment {
+                assignee: responder.clone(),
+                previous: None,
+                actor: responder.clone(),
+                reason: "acknowledged".to_string(),
+                timestamp: timestamp.clone(),
+            };
+            self.record_assignment(&mut incident, "assigned", assignment)?;
+        }
+        
+        if incident.status == IncidentStatus::Open {
+            self.transition(&mut incident, IncidentStatus::Acknowledged, responder.clone(), String::new(), timestamp)?;
+        }
+        
+        let result = json!({
             "incident_id": incident_id,
-            "action_type": action_type,
-            "details": details,
-            "timestamp": timestamp
+            "acknowledged_by": responder,
+            "assignee": incident.assignee,
+            "status": incident.status
         });
+        self.incidents.insert(incident_id, incident);
         
-        Ok(format!("{{\"status\": \"logged\", \"action_type\": \"{}\"}}", action_type))
+        Ok(result.to_string())
     }
 
     #[mutate]
-    async fn update_status(
+    async fn assign_incident(
         &mut self,
         incident_id: String,
-        status: String,
-        notes: String,
+        assignee: String,
+        actor: String,
+        timestamp: String,
+    ) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("assign_incident", Role::Responder, &timestamp)?;
+        let mut incident = self.load_for_event(&incident_id, &timestamp)?;
+        incident.ensure_active()?;
+        
+        if let Some(current) = &incident.assignee {
+            return Err(format!(
+                "Incident {} is already assigned to {}; use reassign_incident",
+                incident_id, current
+            ));
+        }
+        
+        let assignment = Assignment {
+            assignee: assignee.clone(),
+            previous: None,
+            actor,
+            reason: String::new(),
+            timestamp,
+        };
+        self.record_assignment(&mut incident, "assigned", assignment)?;
+        self.incidents.insert(incident_id.clone(), incident);
+        
+        Ok(json!({ "incident_id": incident_id, "assignee": assignee }).to_string())
+    }
+
+    #[mutate]
+    async fn reassign_incident(
+        &mut self,
+        incident_id: String,
+        assignee: String,
+        actor: String,
+        reason: String,
+        timestamp: String,
     ) -> Result<String, String> {
-        let update_data = json!({
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("reassign_incident", Role::Responder, &timestamp)?;
+        let mut incident = self.load_for_event(&incident_id, &timestamp)?;
+        incident.ensure_active()?;
+        
+        let previous = incident
+            .assignee
+            .clone()
+            .ok_or_else(|| format!("Incident {} is unassigned; use assign_incident", incident_id))?;
+        if previous == assignee {
+            return Err(format!("Incident {} is already assigned to {}", incident_id, assignee));
+        }
+        
+        let assignment = Assignment {
+            assignee: assignee.clone(),
+            previous: Some(previous.clone()),
+            actor,
+            reason,
+            timestamp,
+        };
+        self.record_assignment(&mut incident, "reassigned", assignment)?;
+        self.incidents.insert(incident_id.clone(), incident);
+        
+        Ok(json!({
             "incident_id": incident_id,
-            "status": status,
-            "notes": notes,
-            "updated_at": "2026-01-05T10:00:00Z"
+            "assignee": assignee,
+            "previous_assignee": previous
+        }).to_string())
+    }
+
+    #[mutate]
+    async fn link_incidents(
+        &mut self,
+        incident_id: String,
+        target_id: String,
+        relation: String,
+        actor: String,
+        timestamp: String,
+    ) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("link_incidents", Role::Responder, &timestamp)?;
+        let kind: LinkKind = relation.parse()?;
+        let mut incident = self.load_for_event(&incident_id, &timestamp)?;
+        let mut target = self.load_for_event(&target_id, &timestamp)?;
+        
+        self.link(&mut incident, &mut target, kind, &actor, &timestamp)?;
+        self.incidents.insert(incident_id.clone(), incident);
+        self.incidents.insert(target_id.clone(), target);
+        
+        Ok(json!({ "incident_id": incident_id, "linked_to": target_id, "relation": kind }).to_string())
+    }
+
+    #[mutate]
+    async fn unlink_incident(&mut self, incident_id: String, actor: String, timestamp: String) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("unlink_incident", Role::IncidentCommander, &timestamp)?;
+        let mut incident = self.load_for_event(&incident_id, &timestamp)?;
+        let target = self.unlink(&mut incident, &actor, &timestamp)?;
+        let target_id = target.id.clone();
+        self.incidents.insert(incident_id.clone(), incident);
+        self.incidents.insert(target_id.clone(), target);
+        
+        Ok(json!({ "incident_id": incident_id, "unlinked_from": target_id }).to_string())
+    }
+
+    #[mutate]
+    async fn merge_incidents(
+        &mut self,
+        source_id: String,
+        survivor_id: String,
+        actor: String,
+        timestamp: String,
+    ) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("merge_incidents", Role::IncidentCommander, &timestamp)?;
+        let mut source = self.load_for_event(&source_id, &timestamp)?;
+        let mut survivor = self.load_for_event(&survivor_id, &timestamp)?;
+        
+        let copied = self.merge(&mut source, &mut survivor, &actor, &timestamp)?;
+        self.incidents.insert(source_id.clone(), source);
+        self.incidents.insert(survivor_id.clone(), survivor);
+        
+        Ok(json!({
+            "merged": source_id,
+            "into": survivor_id,
+            "timeline_entries_copied": copied
+        }).to_string())
+    }
+
+    #[mutate]
+    async fn import_incidents(&mut self, batch: String, format: String, actor: String, timestamp: String) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("import_incidents", Role::Admin, &timestamp)?;
+        let rows = parse_batch(&batch, &format)?;
+        let now = parse_timestamp(&timestamp)?;
+        
+        // Each row stands alone: a bad row is reported and the rest still import
+        let mut results = Vec::new();
+        for (i, row) in rows.into_iter().enumerate() {
+            let external_id = row.as_ref().ok().map(|row| row.external_id.trim().to_string());
+            let imported = row
+                .and_then(|row| row.validate(now))
+                .and_then(|row| self.import_incident(row, &actor, &timestamp));
+            let (incident_id, error) = match imported {
+                Ok(incident_id) => (Some(incident_id), None),
+                Err(error) => (None, Some(error)),
+            };
+            results.push(RowResult { row: i + 1, external_id, incident_id, error });
+        }
+        
+        let imported = results.iter().filter(|r| r.incident_id.is_some()).count();
+        Ok(json!({
+            "total": results.len(),
+            "imported": imported,
+            "failed": results.len() - imported,
+            "results": results
+        }).to_string())
+    }
+
+    #[mutate]
+    async fn grant_role(&mut self, identity: String, role: String, timestamp: String) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        let caller = self.authorize("grant_role", Role::Admin, &timestamp)?;
+        let role: Role = role.parse()?;
+        self.set_role(&identity, Some(role))?;
+        
+        Ok(json!({ "identity": identity, "role": role, "granted_by": caller }).to_string())
+    }
+
+    #[mutate]
+    async fn revoke_role(&mut self, identity: String, timestamp: String) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        let caller = self.authorize("revoke_role", Role::Admin, &timestamp)?;
+        let previous = self
+            .role_of(&identity)
+            .ok_or_else(|| format!("{} holds no role", identity))?;
+        self.set_role(&identity, None)?;
+        
+        Ok(json!({ "identity": identity, "revoked": previous, "revoked_by": caller }).to_string())
+    }
+
+    #[query]
+    async fn get_role(&self, identity: String) -> Result<String, String> {
+        let identity = if identity.is_empty() { Runtime::sender() } else { identity };
+        Ok(json!({ "identity": identity, "role": self.role_of(&identity) }).to_string())
+    }
+
+    #[query]
+    async fn get_access_denials(&self, limit: u32) -> Result<String, String> {
+        let total = self.access_denials.len();
+        let limit = if limit == 0 { 50 } else { limit as usize };
+        let denials: Vec<AccessDenial> = (total.saturating_sub(limit)..total)
+            .filter_map(|i| self.access_denials.get(i))
+            .collect();
+        
+        Ok(json!({ "total": total, "denials": denials }).to_string())
+    }
+
+    #[mutate]
+    async fn add_comment(
+        &mut self,
+        incident_id: String,
+        author: String,
+        text: String,
+        visibility: String,
+        timestamp: String,
+    ) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        let caller = self.authorize("add_comment", Role::Responder, &timestamp)?;
+        let visibility: Visibility = if visibility.trim().is_empty() {
+            Visibility::Internal
+        } else {
+            visibility.parse()?
+        };
+        if text.trim().is_empty() {
+            return Err("Comment text must not be empty".to_string());
+        }
+        
+        let mut incident = self.load_for_event(&incident_id, &timestamp)?;
+        incident.ensure_not_merged()?;
+        incident.comment_count += 1;
+        let comment_id = format!("C{}", incident.comment_count);
+        
+        let comment = IncidentComment {
+            id: comment_id.clone(),
+            incident_id: incident_id.clone(),
+            author,
+            author_identity: caller,
+            visibility,
+            text,
+            created_at: timestamp.clone(),
+            edited_at: None,
+            revisions: Vec::new(),
+        };
+        let details = json!({
+            "comment_id": comment.id,
+            "author": comment.author,
+            "visibility": comment.visibility,
+            "text": comment.text
+        });
+        
+        self.index_document(DocRef::new(&incident_id, Field::Comment, &comment_id), &comment.text);
+        self.comments.insert(comment_key(&incident_id, &comment_id), comment);
+        self.incidents.insert(incident_id.clone(), incident);
+        self.append_log(&incident_id, "comment_added", details.to_string(), timestamp);
+        
+        Ok(json!({ "incident_id": incident_id, "comment_id": comment_id, "visibility": visibility }).to_string())
+    }
+
+    #[mutate]
+    async fn edit_comment(
+        &mut self,
+        incident_id: String,
+        comment_id: String,
+        text: String,
+        editor: String,
+        timestamp: String,
+    ) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        let caller = self.authorize("edit_comment", Role::Responder, &timestamp)?;
+        self.load_for_event(&incident_id, &timestamp)?.ensure_not_merged()?;
+        let key = comment_key(&incident_id, &comment_id);
+        let mut comment = self
+            .comments
+            .get(&key)
+            .ok_or_else(|| format!("Comment {} not found on incident {}", comment_id, incident_id))?;
+        
+        // Authors may edit their own comments; commanders may edit anyone's
+        if comment.author_identity != caller {
+            self.authorize("edit_comment", Role::IncidentCommander, &timestamp)?;
+        }
+        if text.trim().is_empty() {
+            return Err("Comment text must not be empty".to_string());
+        }
+        if text == comment.text {
+            return Err(format!("Comment {} is unchanged", comment_id));
+        }
+        
+        let previous = std::mem::replace(&mut comment.text, text);
+        comment.revisions.push(CommentRevision {
+            text: previous,
+            edited_by: editor.clone(),
+            timestamp: timestamp.clone(),
+        });
+        comment.edited_at = Some(timestamp.clone());
+        
+        let details = json!({
+            "comment_id": comment_id,
+            "editor": editor,
+            "visibility": comment.visibility,
+            "text": comment.text,
+            "revision": comment.revisions.len()
         });
+        self.index_document(DocRef::new(&incident_id, Field::Comment, &comment_id), &comment.text);
+        self.comments.insert(key, comment);
+        self.append_log(&incident_id, "comment_edited", details.to_string(), timestamp);
         
-        Ok(format!("{{\"incident_id\": \"{}\", \"new_status\": \"{}\"}}", incident_id, status))
+        Ok(json!({ "incident_id": incident_id, "comment_id": comment_id, "status": "edited" }).to_string())
     }
 
     #[query]
-    async fn get_incident_timeline(&self, incident_id: String) -> Result<String, String> {
-        let timeline = json!({
+    async fn list_comments(&self, incident_id: String, visibility: String) -> Result<String, String> {
+        let incident = self.load_incident(&incident_id)?;
+        let visibility: Option<Visibility> = if visibility.trim().is_empty() {
+            None
+        } else {
+            Some(visibility.parse()?)
+        };
+        
+        let comments: Vec<IncidentComment> = (1..=incident.comment_count)
+            .filter_map(|n| self.comments.get(&comment_key(&incident_id, &format!("C{}", n))))
+            .filter(|comment| visibility.is_none_or(|v| comment.visibility == v))
+            .collect();
+        
+        Ok(json!({ "incident_id": incident_id, "count": comments.len(), "comments": comments }).to_string())
+    }
+
+    #[mutate]
+    async fn attach_evidence(
+        &mut self,
+        incident_id: String,
+        name: String,
+        content_type: String,
+        content: String,
+        encoding: String,
+        uploader: String,
+        timestamp: String,
+    ) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("attach_evidence", Role::Responder, &timestamp)?;
+        let bytes = decode_content(&content, &encoding)?;
+        let mut incident = self.load_for_event(&incident_id, &timestamp)?;
+        incident.ensure_not_merged()?;
+        
+        let hash = content_hash(&bytes);
+        if incident.attachments.iter().any(|a| a.hash == hash) {
+            return Err(format!("Evidence {} is already attached to {}", hash, incident_id));
+        }
+        let total: u64 = incident.attachments.iter().map(|a| a.size).sum::<u64>() + bytes.len() as u64;
+        if total > MAX_INCIDENT_EVIDENCE_BYTES {
+            return Err(format!(
+                "Incident {} would hold {} bytes of evidence; the limit is {} bytes",
+                incident_id, total, MAX_INCIDENT_EVIDENCE_BYTES
+            ));
+        }
+        
+        let attachment = Attachment {
+            hash: hash.clone(),
+            name,
+            content_type,
+            size: bytes.len() as u64,
+            uploaded_by: uploader,
+            uploaded_at: timestamp.clone(),
+        };
+        let details = serde_json::to_string(&attachment).map_err(|e| e.to_string())?;
+        
+        // Identical content shares one blob across incidents
+        if self.evidence.get(&hash).is_none() {
+            self.evidence.insert(hash.clone(), bytes);
+        }
+        incident.attachments.push(attachment);
+        self.incidents.insert(incident_id.clone(), incident);
+        self.append_log(&incident_id, "evidence_attached", details, timestamp);
+        
+        Ok(json!({ "incident_id": incident_id, "hash": hash }).to_string())
+    }
+
+    #[query]
+    async fn list_evidence(&self, incident_id: String) -> Result<String, String> {
+        let incident = self.load_incident(&incident_id)?;
+        Ok(json!({
+            "incident_id": incident_id,
+            "count": incident.attachments.len(),
+            "attachments": incident.attachments
+        }).to_string())
+    }
+
+    #[query]
+    async fn get_evidence(&self, hash: String) -> Result<ByteStream, String> {
+        let hash = hash.trim().to_lowercase();
+        let bytes = self
+            .evidence
+            .get(&hash)
+            .ok_or_else(|| format!("Evidence {} not found", hash))?;
+        if content_hash(&bytes) != hash {
+            return Err(format!("Evidence {} does not match its hash", hash));
+        }
+        
+        Ok(ByteStream::from(bytes))
+    }
+
+    #[mutate]
+    async fn add_action_item(
+        &mut self,
+        incident_id: String,
+        title: String,
+        owner: String,
+        due_date: String,
+        priority: String,
+        actor: String,
+        timestamp: String,
+    ) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("add_action_item", Role::Responder, &timestamp)?;
+        let priority: Severity = priority.parse()?;
+        let due_date = parse_due_date(&due_date)?;
+        if title.trim().is_empty() {
+            return Err("Action item title must not be empty".to_string());
+        }
+        
+        // Follow-ups are usually filed after resolution, so closed incidents accept them too
+        let mut incident = self.load_for_event(&incident_id, &timestamp)?;
+        incident.ensure_not_merged()?;
+        incident.action_item_count += 1;
+        let item_id = format!("A{}", incident.action_item_count);
+        
+        let item = ActionItem {
+            id: item_id.clone(),
+            incident_id: incident_id.clone(),
+            title: title.trim().to_string(),
+            owner: parse_owner(&owner),
+            due_date,
+            priority,
+            status: ItemStatus::Open,
+            created_by: actor,
+            created_at: timestamp.clone(),
+            updated_at: timestamp.clone(),
+            completed_at: None,
+            history: Vec::new(),
+        };
+        let details = serde_json::to_string(&item).map_err(|e| e.to_string())?;
+        
+        self.action_items.insert(action_item_key(&incident_id, &item_id), item);
+        self.incidents.insert(incident_id.clone(), incident);
+        self.append_log(&incident_id, "action_item_added", details, timestamp);
+        
+        Ok(json!({ "incident_id": incident_id, "item_id": item_id, "priority": priority }).to_string())
+    }
+
+    #[mutate]
+    async fn update_action_item(
+        &mut self,
+        incident_id: String,
+        item_id: String,
+        owner: String,
+        due_date: String,
+        priority: String,
+        status: String,
+        actor: String,
+        timestamp: String,
+    ) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("update_action_item", Role::Responder, &timestamp)?;
+        self.load_for_event(&incident_id, &timestamp)?.ensure_not_merged()?;
+        let key = action_item_key(&incident_id, &item_id);
+        let item = self
+            .action_items
+            .get(&key)
+            .ok_or_else(|| format!("Action item {} not found on incident {}", item_id, incident_id))?;
+        
+        let update = ActionItemUpdate { owner, due_date, priority, status };
+        let updated = update.apply(&item, &actor, &timestamp)?;
+        let changes = &updated.history[item.history.len()..];
+        let details = json!({ "item_id": item_id, "changes": changes });
+        let result = json!({
             "incident_id": incident_id,
-            "timeline": [
-                {
-                    "timestamp": "2026-01-05T10:00:00Z",
-                    "event": "Incident created",
-                    "details": "Initial report"
-                }
-            ]
+            "item_id": item_id,
+            "status": updated.status,
+            "owner": updated.owner
         });
         
-        Ok(timeline.to_string())
+        self.action_items.insert(key, updated);
+        self.append_log(&incident_id, "action_item_updated", details.to_string(), timestamp);
+        
+        Ok(result.to_string())
     }
 
     #[query]
-    async fn get_active_incidents(&self) -> Result<String, String> {
-        let active_incidents = json!({
-            "count": 0,
-            "incidents": []
-        });
+    async fn list_action_items(&self, incident_id: String, status: String) -> Result<String, String> {
+        let incident = self.load_incident(&incident_id)?;
+        let status: Option<ItemStatus> = if status.trim().is_empty() {
+            None
+        } else {
+            Some(status.parse()?)
+        };
         
-        Ok(active_incidents.to_string())
+        let items: Vec<ActionItem> = self
+            .load_action_items(&incident_id, incident.action_item_count)
+            .into_iter()
+            .filter(|item| status.is_none_or(|s| item.status == s))
+            .collect();
+        
+        Ok(json!({ "incident_id": incident_id, "count": items.len(), "action_items": items }).to_string())
+    }
+
+    #[query]
+    async fn get_overdue_action_items(&self, as_of: String) -> Result<String, String> {
+        let as_of = if as_of.trim().is_empty() {
+            format_timestamp(parse_block_time(&Runtime::block_timestamp())?)
+        } else {
+            normalize_timestamp(&as_of)?
+        };
+        let mut items: Vec<ActionItem> = self
+            .all_incidents()
+            .iter()
+            .flat_map(|incident| self.load_action_items(&incident.id, incident.action_item_count))
+            .filter(|item| item.is_overdue(&as_of))
+            .collect();
+        
+        // Most urgent first, then the longest overdue
+        items.sort_by(|a, b| a.priority.cmp(&b.priority).then_with(|| a.due_date.cmp(&b.due_date)));
+        
+        Ok(json!({ "as_of": as_of, "count": items.len(), "action_items": items }).to_string())
+    }
+
+    #[mutate]
+    async fn update_impact(
+        &mut self,
+        incident_id: String,
+        started_at: String,
+        ended_at: String,
+        regions: String,
+        affected_users: String,
+        components: String,
+        actor: String,
+        timestamp: String,
+    ) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("update_impact", Role::Responder, &timestamp)?;
+        let mut incident = self.load_for_event(&incident_id, &timestamp)?;
+        incident.ensure_not_merged()?;
+        
+        let update = ImpactUpdate { started_at, ended_at, regions, affected_users, components };
+        let impact = incident.impact.apply(&update, &timestamp)?;
+        let changes = incident.impact.diff(&impact, &actor, &timestamp);
+        if changes.is_empty() {
+            return Err(format!("Impact on incident {} is unchanged", incident_id));
+        }
+        
+        let details = json!({ "impact": impact, "changes": changes });
+        incident.impact = impact;
+        incident.impact_history.extend(changes);
+        incident.updated_at = timestamp.clone();
+        self.incidents.insert(incident_id.clone(), incident);
+        self.append_log(&incident_id, "impact_updated", details.to_string(), timestamp);
+        
+        Ok(json!({ "incident_id": incident_id, "impact": details["impact"] }).to_string())
+    }
+
+    #[mutate]
+    async fn add_labels(&mut self, incident_id: String, labels: String, actor: String, timestamp: String) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("add_labels", Role::Responder, &timestamp)?;
+        let labels = parse_labels(&labels)?;
+        let mut incident = self.load_for_event(&incident_id, &timestamp)?;
+        incident.ensure_not_merged()?;
+        
+        let mut changed = Labels::new();
+        for (key, value) in labels {
+            if incident.labels.get(&key) != Some(&value) {
+                incident.labels.insert(key.clone(), value.clone());
+                changed.insert(key, value);
+            }
+        }
+        if incident.labels.len() > MAX_LABELS {
+            return Err(format!("Incident {} would have {} labels; the limit is {}", incident_id, incident.labels.len(), MAX_LABELS));
+        }
+        if changed.is_empty() {
+            return Err(format!("Incident {} already has these labels", incident_id));
+        }
+        
+        incident.updated_at = timestamp.clone();
+        let details = json!({ "labels": changed, "actor": actor });
+        let result = json!({ "incident_id": incident_id, "labels": incident.labels });
+        self.incidents.insert(incident_id.clone(), incident);
+        self.append_log(&incident_id, "labels_added", details.to_string(), timestamp);
+        
+        Ok(result.to_string())
+    }
+
+    #[mutate]
+    async fn remove_labels(&mut self, incident_id: String, keys: String, actor: String, timestamp: String) -> Result<String, String> {
+        let timestamp = self.event_time(&timestamp)?;
+        self.authorize("remove_labels", Role::Responder, &timestamp)?;
+        let keys = parse_keys(&keys)?;
+        let mut incident = self.load_for_event(&incident_id, &timestamp)?;
+        incident.ensure_not_merged()?;
+        
+        let removed: Labels = keys
+            .iter()
+            .filter_map(|key| incident.labels.remove_entry(key))
+            .collect();
+        if removed.is_empty() {
+            return Err(format!("Incident {} has none of the labels {}", incident_id, keys.join(", ")));
+        }
+        
+        incident.updated_at = timestamp.clone();
+        let details = json!({ "labels": removed, "actor": actor });
+        let result = json!({ "incident_id": incident_id, "labels": incident.labels });
+        self.incidents.insert(incident_id.clone(), incident);
+        self.append_log(&incident_id, "labels_removed", details.to_string(), timestamp);
+        
+        Ok(result.to_string())
+    }
+
+    #[query]
+    async fn get_incident_timeline(&self, incident_id: String) -> Result<String, String> {
+        let incident = self.load_incident(&incident_id)?;
+        let entries = self.load_timeline(&incident_id);
+        
+        let timeline = json!({
+            "incident_id": incident_id,
+            "impact": incident.impact,
+            "impact_history": incident.impact_history,
+            "count": entries.len(),
+            "timeline": entries
+        });
+        
+        Ok(timeline.to_string())
+    }
+
+    #[query]
+    async fn verify_incident_integrity(&self, incident_id: String) -> Result<String, String> {
+        self.load_incident(&incident_id)?;
+        let chain = self.load_chain(&incident_id);
+        let broken = verify_chain(&incident_id, &chain);
+        let hashes: Vec<String> = chain.iter().flatten().map(|entry| entry.hash.clone()).collect();
+        
+        Ok(json!({
+            "incident_id": incident_id,
+            "entries": chain.len(),
+            "valid": broken.is_none(),
+            "first_broken_link": broken,
+            "head_hash": hashes.last(),
+            "merkle_root": merkle_root(&hashes)
+        }).to_string())
+    }
+
+    #[query]
+    async fn get_merkle_root(&self, incident_id: String) -> Result<String, String> {
+        self.load_incident(&incident_id)?;
+        let hashes: Vec<String> = self
+            .load_chain(&incident_id)
+            .into_iter()
+            .flatten()
+            .map(|entry| entry.hash)
+            .collect();
+        
+        Ok(json!({
+            "incident_id": incident_id,
+            "entries": hashes.len(),
+            "merkle_root": merkle_root(&hashes),
+            "scheme": MERKLE_SCHEME,
+            "leaves": hashes
+        }).to_string())
+    }
+
+    #[query]
+    async fn generate_postmortem(&self, incident_id: String, format: String) -> Result<String, String> {
+        let incident = self.load_incident(&incident_id)?;
+        let timeline = self.load_timeline(&incident_id);
+        let action_items = self.load_action_items(&incident_id, incident.action_item_count);
+        let report = PostMortem::build(&incident, &timeline, &action_items);
+        
+        match format.trim().to_lowercase().as_str() {
+            "" | "markdown" | "md" => Ok(report.to_markdown()),
+            "json" => serde_json::to_string(&report).map_err(|e| e.to_string()),
+            _ => Err(format!("Unknown format '{}' (expected markdown or json)", format)),
+        }
+    }
+
+    #[query]
+    async fn search_incidents(&self, query: String, limit: u32) -> Result<String, String> {
+        let results = self.search(&query, limit)?;
+        
+        Ok(json!({
+            "query": query,
+            "count": results.len(),
+            "results": results
+        }).to_string())
+    }
+
+    #[query]
+    async fn get_active_incidents(&self) -> Result<String, String> {
+        let mut incidents: Vec<Incident> = self
+            .all_incidents()
+            .into_iter()
+            .filter(|incident| incident.is_active())
+            .collect();
+        
+        // Unassigned incidents first, otherwise keep creation order
+        incidents.sort_by_key(|incident| incident.assignee.is_some());
+        
+        let active_incidents = json!({
+            "count": incidents.len(),
+            "incidents": incidents
+        });
+        
+        Ok(active_incidents.to_string())
+    }
+
+    #[query]
+    async fn query_incidents(
+        &self,
+        severity: String,
+        service: String,
+        status: String,
+        reporter: String,
+        created_from: String,
+        created_to: String,
+        labels: String,
+        cursor: String,
+        limit: u32,
+    ) -> Result<String, String> {
+        let filter = IncidentFilter::parse(severity, service, status, reporter, created_from, created_to, labels)?;
+        let matching: Vec<Incident> = self
+            .all_incidents()
+            .into_iter()
+            .filter(|incident| filter.matches(incident))
+            .collect();
+        
+        let page = paginate(matching, &cursor, limit);
+        let result = json!({
+            "count": page.incidents.len(),
+            "incidents": page.incidents,
+            "next_cursor": page.next_cursor
+        });
+        
+        Ok(result.to_string())
+    }
+
+    #[query]
+    async fn export_incident_data(
+        &self,
+        dataset: String,
+        format: String,
+        from: String,
+        to: String,
+        cursor: String,
+        limit: u32,
+    ) -> Result<String, String> {
+        let chunk = self.export_chunk(&dataset, &format, &from, &to, &cursor, limit)?;
+        serde_json::to_string(&chunk).map_err(|e| e.to_string())
+    }
+
+    #[query]
+    async fn get_export_schemas(&self) -> Result<String, String> {
+        let schemas: Vec<_> = Dataset::ALL
+            .iter()
+            .map(|dataset| {
+                let schema = dataset.schema();
+                json!({ "dataset": dataset, "schema_version": schema.version, "columns": schema.columns })
+            })
+            .collect();
+        
+        Ok(json!({ "schemas": schemas }).to_string())
+    }
+
+    #[query]
+    async fn get_response_metrics(&self, from: String, to: String) -> Result<String, String> {
+        let metrics = response_metrics(&self.all_incidents(), &from, &to)?;
+        let result = json!({
+            "window": { "from": from, "to": to },
+            "metrics": metrics
+        });
+        
+        Ok(result.to_string())
+    }
+
+    #[query]
+    async fn plot_incidents_per_day(&self, from: String, to: String) -> Result<Plottable, String> {
+        let (samples, start, end) = self.trend_window(&from, &to)?;
+        let series = trends::incidents_per_day(&samples, start, end)
+            .into_iter()
+            .map(|(severity, points)| (severity.to_string(), points))
+            .collect();
+        
+        Ok(time_series_plot("Incidents per day", "incidents", series))
+    }
+
+    #[query]
+    async fn plot_open_incidents(&self, from: String, to: String) -> Result<Plottable, String> {
+        let (samples, start, end) = self.trend_window(&from, &to)?;
+        let series = vec![("open".to_string(), trends::open_incidents(&samples, start, end))];
+        
+        Ok(time_series_plot("Open incidents", "incidents", series))
+    }
+
+    #[query]
+    async fn plot_mttr_per_week(&self, from: String, to: String) -> Result<Plottable, String> {
+        let (samples, start, end) = self.trend_window(&from, &to)?;
+        let series = vec![("MTTR".to_string(), trends::mttr_per_week(&samples, start, end))];
+        
+        Ok(time_series_plot("Mean time to resolve per week", "minutes", series))
+    }
+
+    #[query]
+    fn tools(&self) -> String {
+        r#"[
+  {
+    "type": "function",
+    "function": {
+      "name": "create_incident",
+      "description": "Creates new incident record on-chain and suggests similar past incidents with their resolution notes\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "description": {
+            "type": "string",
+            "description": "incident description\n"
+          },
+          "severity": {
+            "type": "string",
+            "description": "severity P0-P3\n"
+          },
+          "service": {
+            "type": "string",
+            "description": "affected service\n"
+          },
+          "reporter": {
+            "type": "string",
+            "description": "reporter name\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "description",
+          "severity",
+          "service",
+          "reporter",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "log_action",
+      "description": "Logs action taken during incident\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "action_type": {
+            "type": "string",
+            "description": "action type (notified, escalated, paged, war_room_created, status_page_updated, remediation_applied, comment)\n"
+          },
+          "details": {
+            "type": "string",
+            "description": "action details JSON, with fields per action type: notified {channel, recipients?, message?}, escalated {to, reason}, paged {responder, via?}, war_room_created {url}, status_page_updated {status, message}, remediation_applied {action, outcome?}, comment {text}\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "action_type",
+          "details",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "update_status",
+      "description": "Moves incident through its lifecycle, rejecting illegal transitions; linked incidents follow. Closing is blocked while P0 action items are unassigned.\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "status": {
+            "type": "string",
+            "description": "new status (open, acknowledged, investigating, mitigated, resolved, closed)\n"
+          },
+          "notes": {
+            "type": "string",
+            "description": "notes on the transition; when resolving, they become the resolution notes\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is making the change\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "status",
+          "notes",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "change_severity",
+      "description": "Escalates or de-escalates incident severity, flagging new P0s for re-paging\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "severity": {
+            "type": "string",
+            "description": "new severity P0-P3\n"
+          },
+          "reason": {
+            "type": "string",
+            "description": "why the severity changed\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is making the change\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "severity",
+          "reason",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "acknowledge_incident",
+      "description": "Acknowledges an incident, taking ownership if it is unassigned\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "responder": {
+            "type": "string",
+            "description": "responder acknowledging the incident\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "responder",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "assign_incident",
+      "description": "Assigns an unassigned incident to a responder\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "assignee": {
+            "type": "string",
+            "description": "responder who will own the incident\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is making the assignment\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "assignee",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "reassign_incident",
+      "description": "Hands an assigned incident over to another responder\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "assignee": {
+            "type": "string",
+            "description": "responder who will own the incident\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is making the assignment\n"
+          },
+          "reason": {
+            "type": "string",
+            "description": "reason for the handover\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "assignee",
+          "actor",
+          "reason",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "link_incidents",
+      "description": "Links an incident under another as a child or as a duplicate\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID to link\n"
+          },
+          "target_id": {
+            "type": "string",
+            "description": "parent incident ID\n"
+          },
+          "relation": {
+            "type": "string",
+            "description": "relation to the parent (child, duplicate)\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is creating the link\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "target_id",
+          "relation",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "unlink_incident",
+      "description": "Removes an incident's link to its parent\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is removing the link\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "merge_incidents",
+      "description": "Merges a duplicate into a surviving incident, combining their timelines\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "source_id": {
+            "type": "string",
+            "description": "incident ID to merge away\n"
+          },
+          "survivor_id": {
+            "type": "string",
+            "description": "incident ID that survives the merge\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is merging\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "source_id",
+          "survivor_id",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "import_incidents",
+      "description": "Bulk-imports historical incidents (admin only). Each row is validated on its own and errors are reported per row; original IDs and timestamps are kept as external references.\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "batch": {
+            "type": "string",
+            "description": "JSON array of incidents, or CSV with a header row\n"
+          },
+          "format": {
+            "type": "string",
+            "description": "batch format (json, csv), empty for json\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is running the import\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "batch",
+          "format",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "grant_role",
+      "description": "Grants a role to a caller identity (admin only)\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "identity": {
+            "type": "string",
+            "description": "caller identity\n"
+          },
+          "role": {
+            "type": "string",
+            "description": "role (admin, incident_commander, responder, viewer)\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "identity",
+          "role",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "revoke_role",
+      "description": "Revokes a caller identity's role (admin only)\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "identity": {
+            "type": "string",
+            "description": "caller identity\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "identity",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_role",
+      "description": "Gets the role held by a caller identity\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "identity": {
+            "type": "string",
+            "description": "caller identity, empty for the current caller\n"
+          }
+        },
+        "required": [
+          "identity"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_access_denials",
+      "description": "Lists the most recent calls rejected for lacking a role. A rejected mutation fails with an \"Access denied\" error; its denial is kept only where the runtime commits state from failed calls\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "limit": {
+            "type": "integer",
+            "description": "number of entries, 0 for the default of 50\n"
+          }
+        },
+        "required": [
+          "limit"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "add_comment",
+      "description": "Adds a responder comment to an incident\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "author": {
+            "type": "string",
+            "description": "comment author\n"
+          },
+          "text": {
+            "type": "string",
+            "description": "comment text\n"
+          },
+          "visibility": {
+            "type": "string",
+            "description": "internal or public, empty for internal\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "author",
+          "text",
+          "visibility",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "edit_comment",
+      "description": "Edits a comment, keeping earlier versions as revisions\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "comment_id": {
+            "type": "string",
+            "description": "comment ID\n"
+          },
+          "text": {
+            "type": "string",
+            "description": "new comment text\n"
+          },
+          "editor": {
+            "type": "string",
+            "description": "who is editing\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "comment_id",
+          "text",
+          "editor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "list_comments",
+      "description": "Lists an incident's comments with their revision history\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "visibility": {
+            "type": "string",
+            "description": "internal or public, empty for all\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "visibility"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "attach_evidence",
+      "description": "Attaches evidence such as log excerpts, stack traces or screenshots (max 1 MiB each, 10 MiB per incident)\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "name": {
+            "type": "string",
+            "description": "file name\n"
+          },
+          "content_type": {
+            "type": "string",
+            "description": "MIME type, e.g. text/plain or image/png\n"
+          },
+          "content": {
+            "type": "string",
+            "description": "evidence content\n"
+          },
+          "encoding": {
+            "type": "string",
+            "description": "content encoding (text, base64), empty for text\n"
+          },
+          "uploader": {
+            "type": "string",
+            "description": "who is uploading\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "name",
+          "content_type",
+          "content",
+          "encoding",
+          "uploader",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "list_evidence",
+      "description": "Lists evidence attached to an incident\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          }
+        },
+        "required": [
+          "incident_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_evidence",
+      "description": "Streams an evidence blob by its sha256 hash\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "hash": {
+            "type": "string",
+            "description": "sha256 hash of the evidence\n"
+          }
+        },
+        "required": [
+          "hash"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "add_action_item",
+      "description": "Files a follow-up action item on an incident\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "title": {
+            "type": "string",
+            "description": "what needs to be done\n"
+          },
+          "owner": {
+            "type": "string",
+            "description": "who owns the item, empty if unassigned\n"
+          },
+          "due_date": {
+            "type": "string",
+            "description": "due date (RFC 3339 or YYYY-MM-DD), empty for none\n"
+          },
+          "priority": {
+            "type": "string",
+            "description": "priority P0-P3\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is filing the item\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "title",
+          "owner",
+          "due_date",
+          "priority",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "update_action_item",
+      "description": "Updates an action item's owner, due date, priority or status; every change is kept in its history\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "item_id": {
+            "type": "string",
+            "description": "action item ID, e.g. A1\n"
+          },
+          "owner": {
+            "type": "string",
+            "description": "new owner, empty to leave unchanged, - to unassign\n"
+          },
+          "due_date": {
+            "type": "string",
+            "description": "new due date (RFC 3339 or YYYY-MM-DD), empty to leave unchanged, - to clear\n"
+          },
+          "priority": {
+            "type": "string",
+            "description": "new priority P0-P3, empty to leave unchanged\n"
+          },
+          "status": {
+            "type": "string",
+            "description": "new status (open, in_progress, done, wont_do), empty to leave unchanged\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is updating the item\n"
+          },
+          "timestamp": {
+            "type": "string",
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "item_id",
+          "owner",
+          "due_date",
+          "priority",
+          "status",
+          "actor",
+          "timestamp"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "list_action_items",
+      "description": "Lists an incident's action items\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "status": {
+            "type": "string",
+            "description": "status (open, in_progress, done, wont_do), empty for all\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "status"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_overdue_action_items",
+      "description": "Lists open action items past their due date across all incidents, highest priority first\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "as_of": {
+            "type": "string",
+            "description": "RFC 3339 time to check against, empty for the current block time\n"
+          }
+        },
+        "required": [
+          "as_of"
+        ]
+      }
     }
-
-    #[query]
-    fn tools(&self) -> String {
-        r#"[
+  },
   {
     "type": "function",
     "function": {
-      "name": "create_incident",
-      "description": "Creates new incident record on-chain\n",
+      "name": "update_impact",
+      "description": "Records customer impact on an incident; every change is kept in its impact history\n",
       "parameters": {
         "type": "object",
         "properties": {
-          "description": {
+          "incident_id": {
             "type": "string",
-            "description": "incident description\n"
+            "description": "incident ID\n"
           },
-          "severity": {
+          "started_at": {
             "type": "string",
-            "description": "severity P0-P3\n"
+            "description": "when customer impact started (RFC 3339), empty to leave unchanged, - to clear\n"
           },
-          "service": {
+          "ended_at": {
             "type": "string",
-            "description": "affected service\n"
+            "description": "when customer impact ended (RFC 3339), empty to leave unchanged, - to clear\n"
           },
-          "reporter": {
+          "regions": {
             "type": "string",
-            "description": "reporter name\n"
+            "description": "comma-separated affected regions, empty to leave unchanged, - to clear\n"
+          },
+          "affected_users": {
+            "type": "string",
+            "description": "estimated number of affected users, empty to leave unchanged, - to clear\n"
+          },
+          "components": {
+            "type": "string",
+            "description": "comma-separated impacted components, empty to leave unchanged, - to clear\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "who is recording the impact\n"
           },
           "timestamp": {
             "type": "string",
-            "description": "timestamp\n"
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
           }
         },
         "required": [
-          "description",
-          "severity",
-          "service",
-          "reporter",
+          "incident_id",
+          "started_at",
+          "ended_at",
+          "regions",
+          "affected_users",
+          "components",
+          "actor",
           "timestamp"
         ]
       }
@@ -170,8 +2392,8 @@ impl IncidentTracker for IncidentTrackerContractState {
   {
     "type": "function",
     "function": {
-      "name": "log_action",
-      "description": "Logs action taken during incident\n",
+      "name": "add_labels",
+      "description": "Adds or updates key/value labels on an incident\n",
       "parameters": {
         "type": "object",
         "properties": {
@@ -179,23 +2401,23 @@ impl IncidentTracker for IncidentTrackerContractState {
             "type": "string",
             "description": "incident ID\n"
           },
-          "action_type": {
+          "labels": {
             "type": "string",
-            "description": "action type (notified, escalated, resolved)\n"
+            "description": "comma-separated key=value pairs, e.g. region=eu-west,tier=enterprise\n"
           },
-          "details": {
+          "actor": {
             "type": "string",
-            "description": "action details JSON\n"
+            "description": "who is labelling\n"
           },
           "timestamp": {
             "type": "string",
-            "description": "timestamp\n"
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
           }
         },
         "required": [
           "incident_id",
-          "action_type",
-          "details",
+          "labels",
+          "actor",
           "timestamp"
         ]
       }
@@ -204,8 +2426,8 @@ impl IncidentTracker for IncidentTrackerContractState {
   {
     "type": "function",
     "function": {
-      "name": "update_status",
-      "description": "Updates incident status\n",
+      "name": "remove_labels",
+      "description": "Removes labels from an incident by key\n",
       "parameters": {
         "type": "object",
         "properties": {
@@ -213,19 +2435,24 @@ impl IncidentTracker for IncidentTrackerContractState {
             "type": "string",
             "description": "incident ID\n"
           },
-          "status": {
+          "keys": {
             "type": "string",
-            "description": "new status (investigating, resolved, closed)\n"
+            "description": "comma-separated label keys\n"
           },
-          "notes": {
+          "actor": {
+            "type": "string",
+            "description": "who is removing the labels\n"
+          },
+          "timestamp": {
             "type": "string",
-            "description": "resolution notes\n"
+            "description": "RFC 3339 timestamp, empty for the current block time\n"
           }
         },
         "required": [
           "incident_id",
-          "status",
-          "notes"
+          "keys",
+          "actor",
+          "timestamp"
         ]
       }
     }
@@ -234,7 +2461,45 @@ impl IncidentTracker for IncidentTrackerContractState {
     "type": "function",
     "function": {
       "name": "get_incident_timeline",
-      "description": "Retrieves complete incident timeline\n",
+      "description": "Retrieves complete incident timeline, including comments, in chronological order, with the recorded customer impact\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          }
+        },
+        "required": [
+          "incident_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "verify_incident_integrity",
+      "description": "Recomputes the incident's log hash chain and reports the first broken link\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          }
+        },
+        "required": [
+          "incident_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_merkle_root",
+      "description": "Exports the Merkle root over the incident's log entry hashes for external attestation\n",
       "parameters": {
         "type": "object",
         "properties": {
@@ -249,17 +2514,276 @@ impl IncidentTracker for IncidentTrackerContractState {
       }
     }
   },
+  {
+    "type": "function",
+    "function": {
+      "name": "generate_postmortem",
+      "description": "Renders a post-incident report from the stored incident and timeline\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "incident_id": {
+            "type": "string",
+            "description": "incident ID\n"
+          },
+          "format": {
+            "type": "string",
+            "description": "output format (markdown, json)\n"
+          }
+        },
+        "required": [
+          "incident_id",
+          "format"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "search_incidents",
+      "description": "Full-text search over descriptions, status change notes, comments and action details, best matches first\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "query": {
+            "type": "string",
+            "description": "words or phrase to search for, e.g. connection pool exhausted\n"
+          },
+          "limit": {
+            "type": "integer",
+            "description": "maximum number of incidents, 0 for the default of 10 (max 50)\n"
+          }
+        },
+        "required": [
+          "query",
+          "limit"
+        ]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
       "name": "get_active_incidents",
-      "description": "Gets all active incidents\n",
+      "description": "Gets all active incidents, unassigned first\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "query_incidents",
+      "description": "Searches incidents with filters and cursor-based pagination\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "severity": {
+            "type": "string",
+            "description": "comma-separated severities to include, empty for all\n"
+          },
+          "service": {
+            "type": "string",
+            "description": "affected service, empty for all\n"
+          },
+          "status": {
+            "type": "string",
+            "description": "comma-separated statuses to include, empty for all; merged incidents match no status\n"
+          },
+          "reporter": {
+            "type": "string",
+            "description": "reporter name, empty for all\n"
+          },
+          "created_from": {
+            "type": "string",
+            "description": "earliest created_at to include (inclusive), empty for no bound\n"
+          },
+          "created_to": {
+            "type": "string",
+            "description": "latest created_at to include (exclusive), empty for no bound\n"
+          },
+          "labels": {
+            "type": "string",
+            "description": "label selector such as region=eu-west,tier!=free, empty for all\n"
+          },
+          "cursor": {
+            "type": "string",
+            "description": "next_cursor from the previous page, empty for the first page\n"
+          },
+          "limit": {
+            "type": "integer",
+            "description": "page size, 0 for the default of 25 (max 100)\n"
+          }
+        },
+        "required": [
+          "severity",
+          "service",
+          "status",
+          "reporter",
+          "created_from",
+          "created_to",
+          "labels",
+          "cursor",
+          "limit"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "export_incident_data",
+      "description": "Exports one chunk of incident history as CSV or NDJSON for warehouse loading\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "dataset": {
+            "type": "string",
+            "description": "dataset to export (incidents, transitions, actions)\n"
+          },
+          "format": {
+            "type": "string",
+            "description": "output format (csv, ndjson), empty for csv\n"
+          },
+          "from": {
+            "type": "string",
+            "description": "window start (inclusive, RFC 3339), empty for no bound\n"
+          },
+          "to": {
+            "type": "string",
+            "description": "window end (exclusive, RFC 3339), empty for no bound\n"
+          },
+          "cursor": {
+            "type": "string",
+            "description": "next_cursor from the previous chunk, empty for the first chunk\n"
+          },
+          "limit": {
+            "type": "integer",
+            "description": "rows per chunk, 0 for the default of 500 (max 2000)\n"
+          }
+        },
+        "required": [
+          "dataset",
+          "format",
+          "from",
+          "to",
+          "cursor",
+          "limit"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_export_schemas",
+      "description": "Lists the versioned column schemas used by export_incident_data\n",
       "parameters": {
         "type": "object",
         "properties": {},
         "required": []
       }
     }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_response_metrics",
+      "description": "Computes MTTA, MTTM and MTTR for incidents reported in a time window, grouped by service and severity. Merged duplicates are left out\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "from": {
+            "type": "string",
+            "description": "window start (inclusive, RFC 3339), empty for no bound\n"
+          },
+          "to": {
+            "type": "string",
+            "description": "window end (exclusive, RFC 3339), empty for no bound\n"
+          }
+        },
+        "required": [
+          "from",
+          "to"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "plot_incidents_per_day",
+      "description": "Plots incidents reported per day, one series per severity\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "from": {
+            "type": "string",
+            "description": "window start (RFC 3339), empty for the first incident or at most 366 days before the end\n"
+          },
+          "to": {
+            "type": "string",
+            "description": "window end (RFC 3339), empty for the latest activity\n"
+          }
+        },
+        "required": [
+          "from",
+          "to"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "plot_open_incidents",
+      "description": "Plots the number of open incidents at the end of each day; a merged duplicate stops counting once merged\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "from": {
+            "type": "string",
+            "description": "window start (RFC 3339), empty for the first incident or at most 366 days before the end\n"
+          },
+          "to": {
+            "type": "string",
+            "description": "window end (RFC 3339), empty for the latest activity\n"
+          }
+        },
+        "required": [
+          "from",
+          "to"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "plot_mttr_per_week",
+      "description": "Plots mean time to resolve in minutes per week of resolution\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "from": {
+            "type": "string",
+            "description": "window start (RFC 3339), empty for the first incident or at most 366 days before the end\n"
+          },
+          "to": {
+            "type": "string",
+            "description": "window end (RFC 3339), empty for the latest activity\n"
+          }
+        },
+        "required": [
+          "from",
+          "to"
+        ]
+      }
+    }
   }
 ]"#.to_string()
     }
@@ -0,0 +1,186 @@
+use crate::{Incident, IncidentTrackerContractState, TimelineEntry};
+use incident_common::IncidentStatus;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    Child,
+    Duplicate,
+}
+
+impl FromStr for LinkKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "child" => Ok(LinkKind::Child),
+            "duplicate" => Ok(LinkKind::Duplicate),
+            _ => Err(format!("Unknown link relation '{}' (expected child or duplicate)", s)),
+        }
+    }
+}
+
+// How an incident relates to the incident it is linked under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentLink {
+    pub kind: LinkKind,
+    pub target: String,
+}
+
+impl IncidentTrackerContractState {
+    // Links `incident` under `target`. Hierarchies are a single level deep, which
+    // keeps propagation simple and makes cycles impossible.
+    pub(crate) fn link(
+        &mut self,
+        incident: &mut Incident,
+        target: &mut Incident,
+        kind: LinkKind,
+        actor: &str,
+        timestamp: &str,
+    ) -> Result<(), String> {
+        if incident.id == target.id {
+            return Err(format!("Cannot link {} to itself", incident.id));
+        }
+        incident.ensure_not_merged()?;
+        target.ensure_not_merged()?;
+        if let Some(link) = &incident.link {
+            return Err(format!("Incident {} is already linked to {}; unlink it first", incident.id, link.target));
+        }
+        if !incident.linked_ids.is_empty() {
+            return Err(format!("Incident {} has linked incidents of its own and cannot be linked", incident.id));
+        }
+        if let Some(link) = &target.link {
+            return Err(format!("Incident {} is itself linked to {}; link to that incident instead", target.id, link.target));
+        }
+
+        incident.link = Some(IncidentLink { kind, target: target.id.clone() });
+        target.linked_ids.push(incident.id.clone());
+
+        let details = json!({ "kind": kind, "incident_id": incident.id, "target": target.id, "actor": actor });
+        self.append_log(&incident.id, "linked", details.to_string(), timestamp.to_string());
+        self.append_log(&target.id, "link_added", details.to_string(), timestamp.to_string());
+        Ok(())
+    }
+
+    pub(crate) fn unlink(&mut self, incident: &mut Incident, actor: &str, timestamp: &str) -> Result<Incident, String> {
+        let link = incident
+            .link
+            .take()
+            .ok_or_else(|| format!("Incident {} is not linked to another incident", incident.id))?;
+        let mut target = self.load_incident(&link.target)?;
+        target.linked_ids.retain(|id| *id != incident.id);
+
+        let details = json!({ "kind": link.kind, "incident_id": incident.id, "target": target.id, "actor": actor });
+        self.append_log(&incident.id, "unlinked", details.to_string(), timestamp.to_string());
+        self.append_log(&target.id, "link_removed", details.to_string(), timestamp.to_string());
+        Ok(target)
+    }
+
+    // Folds `source` into `survivor`: its timeline is copied across (entries keep
+    // their original timestamps and are marked with merged_from), its linked
+    // incidents move to the survivor, and the source stops accepting changes.
+    pub(crate) fn merge(
+        &mut self,
+        source: &mut Incident,
+        survivor: &mut Incident,
+        actor: &str,
+        timestamp: &str,
+    ) -> Result<usize, String> {
+        if source.id == survivor.id {
+            return Err(format!("Cannot merge {} into itself", source.id));
+        }
+        source.ensure_not_merged()?;
+        survivor.ensure_not_merged()?;
+        if survivor.link.as_ref().is_some_and(|link| link.target == source.id) {
+            return Err(format!("Incident {} is linked under {}; merge in the other direction", survivor.id, source.id));
+        }
+        // The source's linked incidents move to the survivor, which would nest
+        // them two levels deep if the survivor is itself linked under another
+        if let (Some(link), false) = (&survivor.link, source.linked_ids.is_empty()) {
+            return Err(format!(
+                "Incident {} is linked under {} and cannot take over the linked incidents of {}; unlink it first",
+                survivor.id, link.target, source.id
+            ));
+        }
+
+        // Detach the source from whatever it was linked under
+        if let Some(link) = source.link.take() {
+            if link.target != survivor.id {
+                let mut old_target = self.load_incident(&link.target)?;
+                old_target.linked_ids.retain(|id| *id != source.id);
+                self.incidents.insert(old_target.id.clone(), old_target);
+            }
+        }
+        survivor.linked_ids.retain(|id| *id != source.id);
+
+        for linked_id in std::mem::take(&mut source.linked_ids) {
+            let mut linked = self.load_incident(&linked_id)?;
+            if let Some(link) = linked.link.as_mut() {
+                link.target = survivor.id.clone();
+            }
+            self.incidents.insert(linked_id.clone(), linked);
+            if !survivor.linked_ids.contains(&linked_id) {
+                survivor.linked_ids.push(linked_id);
+            }
+        }
+
+        let copied: Vec<TimelineEntry> = self.load_timeline(&source.id);
+        for entry in &copied {
+            let mut entry = entry.clone();
+            entry.merged_from = Some(source.id.clone());
+            self.append_entry(&survivor.id, entry);
+        }
+
+        source.merged_into = Some(survivor.id.clone());
+        source.updated_at = timestamp.to_string();
+        survivor.updated_at = timestamp.to_string();
+
+        let details = json!({ "source": source.id, "survivor": survivor.id, "entries": copied.len(), "actor": actor });
+        self.append_log(&source.id, "merged_into", details.to_string(), timestamp.to_string());
+        self.append_log(&survivor.id, "merged", details.to_string(), timestamp.to_string());
+        Ok(copied.len())
+    }
+
+    // Applies a parent's status change to its linked incidents. Incidents for
+    // which the change is not a legal transition, or which cannot be closed
+    // yet, are skipped and reported.
+    pub(crate) fn propagate_status(
+        &mut self,
+        parent: &Incident,
+        to: IncidentStatus,
+        actor: &str,
+        timestamp: &str,
+    ) -> Result<(Vec<String>, Vec<String>), String> {
+        let mut updated = Vec::new();
+        let mut skipped = Vec::new();
+
+        for linked_id in &parent.linked_ids {
+            let mut linked = self.load_incident(linked_id)?;
+            let blocked = to == IncidentStatus::Closed
+                && !self.unassigned_p0_items(linked_id, linked.action_item_count).is_empty();
+            if linked.merged_into.is_some() || linked.status == to || !linked.status.can_transition_to(to) || blocked {
+                skipped.push(linked_id.clone());
+                continue;
+            }
+
+            let notes = format!("Propagated from {}", parent.id);
+            self.transition(&mut linked, to, actor.to_string(), notes, timestamp.to_string())?;
+            self.incidents.insert(linked_id.clone(), linked);
+            updated.push(linked_id.clone());
+        }
+
+        Ok((updated, skipped))
+    }
+
+    // Mirrors an action logged on a parent, such as a notification, into the
+    // timelines of its linked incidents
+    pub(crate) fn propagate_action(&mut self, parent: &Incident, action_type: &str, details: &str, timestamp: &str) {
+        let mirrored = json!({ "parent_id": parent.id, "action_type": action_type, "details": details });
+        for linked_id in &parent.linked_ids {
+            self.append_log(linked_id, "parent_action", mirrored.to_string(), timestamp.to_string());
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::time::parse_timestamp;
+use crate::Incident;
+use incident_common::IncidentStatus;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+// Response durations for a single incident, in seconds since it was reported
+pub struct Durations {
+    pub acknowledge: Option<i64>,
+    pub mitigate: Option<i64>,
+    pub resolve: Option<i64>,
+}
+
+pub fn durations(incident: &Incident) -> Result<Durations, String> {
+    let created = parse_timestamp(&incident.created_at)?;
+    let since_created = |timestamp: &str| parse_timestamp(timestamp).map(|t| t - created);
+
+    let acknowledge = incident.acknowledged_at.as_deref().map(since_created).transpose()?;
+
+    // Time to mitigate counts from the first mitigation; time to resolve uses the
+    // latest resolution, and only while the incident has stayed resolved.
+    let mitigate = incident
+        .status_history
+        .iter()
+        .find(|t| t.to == IncidentStatus::Mitigated)
+        .map(|t| since_created(&t.timestamp))
+        .transpose()?;
+    let resolve = if incident.is_active() {
+        None
+    } else {
+        incident
+            .status_history
+            .iter()
+            .rev()
+            .find(|t| t.to == IncidentStatus::Resolved)
+            .map(|t| since_created(&t.timestamp))
+            .transpose()?
+    };
+
+    Ok(Durations { acknowledge, mitigate, resolve })
+}
+
+#[derive(Default)]
+struct Accumulator {
+    incidents: u64,
+    acknowledge: Vec<i64>,
+    mitigate: Vec<i64>,
+    resolve: Vec<i64>,
+}
+
+#[derive(Serialize)]
+pub struct ResponseStats {
+    pub incidents: u64,
+    pub acknowledged: usize,
+    pub mtta_seconds: Option<i64>,
+    pub mitigated: usize,
+    pub mttm_seconds: Option<i64>,
+    pub resolved: usize,
+    pub mttr_seconds: Option<i64>,
+}
+
+fn mean(values: &[i64]) -> Option<i64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<i64>() / values.len() as i64)
+    }
+}
+
+impl Accumulator {
+    fn add(&mut self, d: &Durations) {
+        self.incidents += 1;
+        self.acknowledge.extend(d.acknowledge);
+        self.mitigate.extend(d.mitigate);
+        self.resolve.extend(d.resolve);
+    }
+
+    fn finish(&self) -> ResponseStats {
+        ResponseStats {
+            incidents: self.incidents,
+            acknowledged: self.acknowledge.len(),
+            mtta_seconds: mean(&self.acknowledge),
+            mitigated: self.mitigate.len(),
+            mttm_seconds: mean(&self.mitigate),
+            resolved: self.resolve.len(),
+            mttr_seconds: mean(&self.resolve),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ResponseMetrics {
+    pub overall: ResponseStats,
+    pub by_service: BTreeMap<String, ResponseStats>,
+    pub by_severity: BTreeMap<String, ResponseStats>,
+    // incidents in the window whose timestamps could not be parsed
+    pub skipped: Vec<String>,
+}
+
+// Mean time to acknowledge, mitigate and resolve for incidents reported in
+// [from, to). Empty bounds leave that side of the window open.
+pub fn response_metrics(incidents: &[Incident], from: &str, to: &str) -> Result<ResponseMetrics, String> {
+    let from = if from.is_empty() { None } else { Some(parse_timestamp(from)?) };
+    let to = if to.is_empty() { None } else { Some(parse_timestamp(to)?) };
+
+    let mut overall = Accumulator::default();
+    let mut by_service: BTreeMap<String, Accumulator> = BTreeMap::new();
+    let mut by_severity: BTreeMap<String, Accumulator> = BTreeMap::new();
+    let mut skipped = Vec::new();
+
+    // Merged duplicates are left out; their history lives on in the survivor
+    for incident in incidents.iter().filter(|incident| incident.merged_into.is_none()) {
+        let created = match parse_timestamp(&incident.created_at) {
+            Ok(created) => created,
+            Err(_) => {
+                skipped.push(incident.id.clone());
+                continue;
+            }
+        };
+        if from.is_some_and(|from| created < from) || to.is_some_and(|to| created >= to) {
+            continue;
+        }
+
+        let d = match durations(incident) {
+            Ok(d) => d,
+            Err(_) => {
+                skipped.push(incident.id.clone());
+                continue;
+            }
+        };
+
+        overall.add(&d);
+        by_service.entry(incident.service.clone()).or_default().add(&d);
+        by_severity.entry(incident.severity.to_string()).or_default().add(&d);
+    }
+
+    Ok(ResponseMetrics {
+        overall: overall.finish(),
+        by_service: by_service.iter().map(|(k, v)| (k.clone(), v.finish())).collect(),
+        by_severity: by_severity.iter().map(|(k, v)| (k.clone(), v.finish())).collect(),
+        skipped,
+    })
+}
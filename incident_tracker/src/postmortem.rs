@@ -0,0 +1,270 @@
+use crate::action_items::ActionItem;
+use crate::actions::RemediationApplied;
+use crate::labels::Labels;
+use crate::time::parse_timestamp;
+use crate::{Incident, SeverityChange, TimelineEntry};
+use incident_common::{IncidentStatus, Severity};
+use serde::Serialize;
+use std::fmt::Write;
+
+#[derive(Serialize)]
+pub struct Summary<'a> {
+    pub description: &'a str,
+    pub service: &'a str,
+    pub reporter: &'a str,
+    pub status: IncidentStatus,
+    pub initial_severity: Severity,
+    pub final_severity: Severity,
+    pub reported_at: &'a str,
+    pub resolved_at: Option<&'a str>,
+    pub resolution_notes: &'a str,
+    pub labels: &'a Labels,
+}
+
+#[derive(Serialize)]
+pub struct ImpactWindow<'a> {
+    pub start: Option<&'a str>,
+    pub end: Option<&'a str>,
+    pub duration_seconds: Option<i64>,
+    // whether start and end were recorded by responders or inferred from status changes
+    pub recorded: bool,
+    pub regions: &'a [String],
+    pub affected_users: Option<u64>,
+    pub components: &'a [String],
+}
+
+// A remediation_applied entry from the timeline: what was done and how it went
+#[derive(Serialize)]
+pub struct Remediation<'a> {
+    pub timestamp: &'a str,
+    pub action: String,
+    pub outcome: String,
+    pub merged_from: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+pub struct PostMortem<'a> {
+    pub incident_id: &'a str,
+    pub summary: Summary<'a>,
+    pub impact: ImpactWindow<'a>,
+    pub severity_history: &'a [SeverityChange],
+    pub responders: Vec<&'a str>,
+    pub timeline: &'a [TimelineEntry],
+    pub remediations: Vec<Remediation<'a>>,
+    pub action_items: &'a [ActionItem],
+}
+
+fn first_transition_to<'a>(incident: &'a Incident, statuses: &[IncidentStatus]) -> Option<&'a str> {
+    incident
+        .status_history
+        .iter()
+        .find(|t| statuses.contains(&t.to))
+        .map(|t| t.timestamp.as_str())
+}
+
+fn remediations(timeline: &[TimelineEntry]) -> Vec<Remediation<'_>> {
+    timeline
+        .iter()
+        .filter(|entry| entry.event == "remediation_applied")
+        .filter_map(|entry| {
+            let applied: RemediationApplied = serde_json::from_str(&entry.details).ok()?;
+            Some(Remediation {
+                timestamp: &entry.timestamp,
+                action: applied.action,
+                outcome: applied.outcome,
+                merged_from: entry.merged_from.as_deref(),
+            })
+        })
+        .collect()
+}
+
+// Everyone who reported, acknowledged, owned or changed the incident, in order of appearance
+fn responders(incident: &Incident) -> Vec<&str> {
+    let mut names: Vec<&str> = Vec::new();
+    let candidates = std::iter::once(incident.reporter.as_str())
+        .chain(incident.acknowledged_by.as_deref())
+        .chain(incident.assignment_history.iter().map(|a| a.assignee.as_str()))
+        .chain(incident.status_history.iter().map(|t| t.actor.as_str()))
+        .chain(incident.severity_history.iter().map(|c| c.actor.as_str()));
+
+    for name in candidates {
+        if !name.trim().is_empty() && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+pub fn format_duration(seconds: i64) -> String {
+    let (hours, minutes, secs) = (seconds / 3600, (seconds % 3600) / 60, seconds % 60);
+    match (hours, minutes) {
+        (0, 0) => format!("{}s", secs),
+        (0, _) => format!("{}m {}s", minutes, secs),
+        _ => format!("{}h {}m", hours, minutes),
+    }
+}
+
+// Markdown table cells cannot contain pipes or line breaks
+fn cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+impl<'a> PostMortem<'a> {
+    pub fn build(incident: &'a Incident, timeline: &'a [TimelineEntry], action_items: &'a [ActionItem]) -> Self {
+        // Unless responders recorded the impact window, it runs from the report
+        // to the first mitigation (or resolution, if nothing was mitigated first)
+        let impact = &incident.impact;
+        let recorded = impact.started_at.is_some();
+        let (start, end) = if recorded {
+            (impact.started_at.as_deref(), impact.ended_at.as_deref())
+        } else {
+            (
+                Some(incident.created_at.as_str()),
+                first_transition_to(incident, &[IncidentStatus::Mitigated, IncidentStatus::Resolved]),
+            )
+        };
+        let duration_seconds = match (start, end) {
+            (Some(start), Some(end)) => match (parse_timestamp(start), parse_timestamp(end)) {
+                (Ok(start), Ok(end)) => Some(end - start),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        PostMortem {
+            incident_id: &incident.id,
+            summary: Summary {
+                description: &incident.description,
+                service: &incident.service,
+                reporter: &incident.reporter,
+                status: incident.status,
+                initial_severity: incident.severity_history.first().map_or(incident.severity, |c| c.from),
+                final_severity: incident.severity,
+                reported_at: &incident.created_at,
+                resolved_at: first_transition_to(incident, &[IncidentStatus::Resolved]),
+                resolution_notes: &incident.notes,
+                labels: &incident.labels,
+            },
+            impact: ImpactWindow {
+                start,
+                end,
+                duration_seconds,
+                recorded,
+                regions: &impact.regions,
+                affected_users: impact.affected_users,
+                components: &impact.components,
+            },
+            severity_history: &incident.severity_history,
+            responders: responders(incident),
+            timeline,
+            remediations: remediations(timeline),
+            action_items,
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let s = &self.summary;
+        let mut md = String::new();
+
+        let _ = writeln!(md, "# Post-incident report: {}\n", self.incident_id);
+        let _ = writeln!(md, "## Summary\n");
+        let _ = writeln!(md, "{}\n", s.description);
+        let _ = writeln!(md, "| Field | Value |\n|---|---|");
+        let _ = writeln!(md, "| Service | {} |", cell(s.service));
+        let _ = writeln!(md, "| Severity | {} (initially {}) |", s.final_severity, s.initial_severity);
+        let _ = writeln!(md, "| Status | {} |", s.status);
+        let _ = writeln!(md, "| Reported by | {} |", cell(s.reporter));
+        let _ = writeln!(md, "| Reported at | {} |", s.reported_at);
+        let _ = writeln!(md, "| Resolved at | {} |", s.resolved_at.unwrap_or("not resolved"));
+        if !s.labels.is_empty() {
+            let labels: Vec<String> = s.labels.iter().map(|(key, value)| format!("`{}={}`", key, value)).collect();
+            let _ = writeln!(md, "| Labels | {} |", labels.join(" "));
+        }
+        if !s.resolution_notes.is_empty() {
+            let _ = writeln!(md, "\n**Resolution notes:** {}", s.resolution_notes);
+        }
+
+        let _ = writeln!(md, "\n## Impact window\n");
+        let _ = writeln!(md, "- Start: {}", self.impact.start.unwrap_or("unknown"));
+        let _ = writeln!(md, "- End: {}", self.impact.end.unwrap_or("ongoing"));
+        if let Some(duration) = self.impact.duration_seconds {
+            let _ = writeln!(md, "- Duration: {}", format_duration(duration));
+        }
+        if !self.impact.recorded {
+            let _ = writeln!(md, "- Inferred from the report and first mitigation; no impact window was recorded");
+        }
+        if !self.impact.regions.is_empty() {
+            let _ = writeln!(md, "- Regions: {}", self.impact.regions.join(", "));
+        }
+        if let Some(users) = self.impact.affected_users {
+            let _ = writeln!(md, "- Affected users (estimate): {}", users);
+        }
+        if !self.impact.components.is_empty() {
+            let _ = writeln!(md, "- Components: {}", self.impact.components.join(", "));
+        }
+
+        let _ = writeln!(md, "\n## Severity history\n");
+        if self.severity_history.is_empty() {
+            let _ = writeln!(md, "No severity changes.");
+        }
+        for change in self.severity_history {
+            let _ = writeln!(
+                md,
+                "- {}: {} → {} by {} ({})",
+                change.timestamp, change.from, change.to, change.actor, change.reason
+            );
+        }
+
+        let _ = writeln!(md, "\n## Responders\n");
+        for responder in &self.responders {
+            let _ = writeln!(md, "- {}", responder);
+        }
+
+        let _ = writeln!(md, "\n## Timeline\n");
+        let _ = writeln!(md, "| Time | Event | Details |\n|---|---|---|");
+        for entry in self.timeline {
+            let event = match &entry.merged_from {
+                Some(source) => format!("{} (from {})", entry.event, source),
+                None => entry.event.clone(),
+            };
+            let _ = writeln!(md, "| {} | {} | {} |", entry.timestamp, cell(&event), cell(&entry.details));
+        }
+
+        let _ = writeln!(md, "\n## Remediation\n");
+        if self.remediations.is_empty() {
+            let _ = writeln!(md, "None recorded.");
+        }
+        for remediation in &self.remediations {
+            let outcome = match remediation.outcome.as_str() {
+                "" => String::new(),
+                outcome => format!(" — {}", outcome),
+            };
+            let source = match remediation.merged_from {
+                Some(source) => format!(" (from {})", source),
+                None => String::new(),
+            };
+            let _ = writeln!(md, "- {}: {}{}{}", remediation.timestamp, remediation.action, outcome, source);
+        }
+
+        let _ = writeln!(md, "\n## Action items\n");
+        if self.action_items.is_empty() {
+            let _ = writeln!(md, "None recorded.");
+        }
+        for item in self.action_items {
+            let checkbox = if item.status.is_pending() { " " } else { "x" };
+            let _ = writeln!(
+                md,
+                "- [{}] {} ({}) {} — owner: {}, due: {}, status: {}",
+                checkbox,
+                item.id,
+                item.priority,
+                item.title,
+                item.owner.as_deref().unwrap_or("unassigned"),
+                item.due_date.as_deref().unwrap_or("none"),
+                item.status
+            );
+        }
+
+        md
+    }
+}
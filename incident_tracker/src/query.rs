@@ -0,0 +1,109 @@
+use crate::labels::LabelSelector;
+use crate::time::normalize_timestamp;
+use crate::Incident;
+use incident_common::{IncidentStatus, Severity};
+
+pub const DEFAULT_PAGE_SIZE: usize = 25;
+pub const MAX_PAGE_SIZE: usize = 100;
+
+// Filters for query_incidents. Empty strings from the MCP call mean "any".
+// Time bounds are normalized to UTC so they compare correctly with stored timestamps.
+#[derive(Debug, Default)]
+pub struct IncidentFilter {
+    pub severities: Vec<Severity>,
+    pub statuses: Vec<IncidentStatus>,
+    pub service: Option<String>,
+    pub reporter: Option<String>,
+    pub created_from: Option<String>,
+    pub created_to: Option<String>,
+    pub labels: LabelSelector,
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+// Parses a comma-separated list such as "P0,P1", rejecting unknown values
+fn parse_list<T: std::str::FromStr<Err = String>>(value: &str) -> Result<Vec<T>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+impl IncidentFilter {
+    pub fn parse(
+        severity: String,
+        service: String,
+        status: String,
+        reporter: String,
+        created_from: String,
+        created_to: String,
+        labels: String,
+    ) -> Result<Self, String> {
+        Ok(IncidentFilter {
+            severities: parse_list(&severity)?,
+            statuses: parse_list(&status)?,
+            service: non_empty(service),
+            reporter: non_empty(reporter),
+            created_from: non_empty(created_from).as_deref().map(normalize_timestamp).transpose()?,
+            created_to: non_empty(created_to).as_deref().map(normalize_timestamp).transpose()?,
+            labels: LabelSelector::parse(&labels)?,
+        })
+    }
+
+    // created_from is inclusive and created_to exclusive. A merged incident
+    // keeps the status it had when merged, so it matches no status filter.
+    pub fn matches(&self, incident: &Incident) -> bool {
+        (self.severities.is_empty() || self.severities.contains(&incident.severity))
+            && (self.statuses.is_empty() || (incident.merged_into.is_none() && self.statuses.contains(&incident.status)))
+            && self.service.as_ref().is_none_or(|s| s.eq_ignore_ascii_case(&incident.service))
+            && self.reporter.as_ref().is_none_or(|r| r.eq_ignore_ascii_case(&incident.reporter))
+            && self.created_from.as_ref().is_none_or(|from| incident.created_at >= *from)
+            && self.created_to.as_ref().is_none_or(|to| incident.created_at < *to)
+            && self.labels.matches(&incident.labels)
+    }
+}
+
+// Incidents are ordered by creation time, then ID, so the order never changes
+// as new incidents arrive. The cursor is the sort key of the last item served.
+fn sort_key(incident: &Incident) -> String {
+    format!("{}|{}", incident.created_at, incident.id)
+}
+
+pub struct Page {
+    pub incidents: Vec<Incident>,
+    pub next_cursor: Option<String>,
+}
+
+pub fn paginate(mut incidents: Vec<Incident>, cursor: &str, limit: u32) -> Page {
+    let limit = match limit as usize {
+        0 => DEFAULT_PAGE_SIZE,
+        n => n.min(MAX_PAGE_SIZE),
+    };
+
+    incidents.sort_by_key(sort_key);
+    let mut remaining: Vec<Incident> = incidents
+        .into_iter()
+        .filter(|incident| cursor.is_empty() || sort_key(incident).as_str() > cursor)
+        .collect();
+
+    let next_cursor = if remaining.len() > limit {
+        remaining.truncate(limit);
+        remaining.last().map(sort_key)
+    } else {
+        None
+    };
+
+    Page {
+        incidents: remaining,
+        next_cursor,
+    }
+}
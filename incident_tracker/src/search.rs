@@ -0,0 +1,311 @@
+use crate::comments::comment_key;
+use crate::{log_key, Incident, IncidentTrackerContractState, StatusTransition};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+pub const DEFAULT_SEARCH_LIMIT: usize = 10;
+pub const MAX_SEARCH_LIMIT: usize = 50;
+// characters of context kept on each side of a match
+const SNIPPET_CONTEXT: usize = 60;
+// documents per stored posting page, so indexing rewrites one small page
+// rather than a term's whole posting list
+const POSTING_PAGE_SIZE: usize = 64;
+
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "is", "it", "of", "on", "or", "the", "to",
+    "was", "were", "with",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Field {
+    Description,
+    Notes,
+    Comment,
+    Action,
+}
+
+impl Field {
+    // Descriptions say what went wrong, so they outrank passing mentions in the log
+    fn weight(self) -> f64 {
+        match self {
+            Field::Description => 3.0,
+            Field::Notes => 2.0,
+            Field::Comment | Field::Action => 1.0,
+        }
+    }
+}
+
+// One indexed piece of text. key is the comment ID for comments, the log seq
+// for actions and status change notes, and empty for fields stored on the
+// incident itself (the description, and resolution notes of imports).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct DocRef {
+    pub incident_id: String,
+    pub field: Field,
+    pub key: String,
+}
+
+impl DocRef {
+    pub fn new(incident_id: &str, field: Field, key: impl ToString) -> Self {
+        DocRef {
+            incident_id: incident_id.to_string(),
+            field,
+            key: key.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchHit {
+    pub field: Field,
+    pub key: String,
+    pub snippet: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResult {
+    pub incident_id: String,
+    pub score: f64,
+    pub severity: incident_common::Severity,
+    pub status: incident_common::IncidentStatus,
+    pub service: String,
+    pub created_at: String,
+    pub matched_terms: Vec<String>,
+    pub hits: Vec<SearchHit>,
+}
+
+pub fn posting_key(term: &str, page: u64) -> String {
+    format!("{}:{:06}", term, page)
+}
+
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| token.len() > 1)
+        .map(str::to_lowercase)
+        .filter(|token| !STOP_WORDS.contains(&token.as_str()))
+        .collect()
+}
+
+// Action details are stored as JSON; only the values are worth searching
+pub fn action_text(details: &str) -> String {
+    fn collect(value: &serde_json::Value, out: &mut Vec<String>) {
+        match value {
+            serde_json::Value::String(s) => out.push(s.clone()),
+            serde_json::Value::Array(items) => items.iter().for_each(|item| collect(item, out)),
+            serde_json::Value::Object(map) => map.values().for_each(|item| collect(item, out)),
+            _ => {}
+        }
+    }
+
+    match serde_json::from_str::<serde_json::Value>(details) {
+        Ok(value) => {
+            let mut out = Vec::new();
+            collect(&value, &mut out);
+            out.join(" ")
+        }
+        Err(_) => details.to_string(),
+    }
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+// Cuts a window of text around the whole phrase if it occurs, otherwise the first matched term
+fn snippet(text: &str, phrase: &str, terms: &[String]) -> String {
+    // ASCII lowercasing keeps byte offsets valid in the original text
+    let haystack = text.to_ascii_lowercase();
+    let found = haystack
+        .find(&phrase.to_ascii_lowercase())
+        .map(|at| (at, phrase.len()))
+        .or_else(|| terms.iter().filter_map(|t| haystack.find(t.as_str()).map(|at| (at, t.len()))).min());
+
+    let (at, len) = found.unwrap_or((0, 0));
+    let start = floor_char_boundary(text, at.saturating_sub(SNIPPET_CONTEXT));
+    let end = ceil_char_boundary(text, (at + len + SNIPPET_CONTEXT).min(text.len()));
+
+    let mut snippet = text[start..end].replace(['\r', '\n'], " ");
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+impl IncidentTrackerContractState {
+    // Adds a document to the last posting page of each of its terms, starting
+    // a new page when it is full. Postings are never removed; search re-reads
+    // the current text, so edited-away terms simply stop matching. Only the
+    // last page is checked for the document, so a re-indexed one may be listed
+    // twice; readers de-duplicate.
+    pub(crate) fn index_document(&mut self, doc: DocRef, text: &str) {
+        let terms: BTreeSet<String> = tokenize(text).into_iter().collect();
+        for term in terms {
+            let pages = self.search_pages.get(&term).unwrap_or(0);
+            let mut last = match pages {
+                0 => Vec::new(),
+                n => self.search_index.get(&posting_key(&term, n - 1)).unwrap_or_default(),
+            };
+            if last.contains(&doc) {
+                continue;
+            }
+            if pages == 0 || last.len() >= POSTING_PAGE_SIZE {
+                self.search_index.insert(posting_key(&term, pages), vec![doc.clone()]);
+                self.search_pages.insert(term, pages + 1);
+            } else {
+                last.push(doc.clone());
+                self.search_index.insert(posting_key(&term, pages - 1), last);
+            }
+        }
+    }
+
+    // Every document a term appears in, across its posting pages
+    pub(crate) fn postings(&self, term: &str) -> Vec<DocRef> {
+        let pages = self.search_pages.get(&term.to_string()).unwrap_or(0);
+        (0..pages)
+            .filter_map(|page| self.search_index.get(&posting_key(term, page)))
+            .flatten()
+            .collect()
+    }
+
+    fn document_text(&self, doc: &DocRef, incident: &Incident) -> Option<String> {
+        match doc.field {
+            Field::Description => Some(incident.description.clone()),
+            Field::Notes if doc.key.is_empty() => Some(incident.notes.clone()),
+            Field::Notes => {
+                let seq = doc.key.parse().ok()?;
+                let entry = self.timeline.get(&log_key(&doc.incident_id, seq))?;
+                serde_json::from_str::<StatusTransition>(&entry.details).ok().map(|t| t.notes)
+            }
+            Field::Comment => self
+                .comments
+                .get(&comment_key(&doc.incident_id, &doc.key))
+                .map(|comment| comment.text),
+            Field::Action => {
+                let seq = doc.key.parse().ok()?;
+                self.timeline
+                    .get(&log_key(&doc.incident_id, seq))
+                    .map(|entry| action_text(&entry.details))
+            }
+        }
+    }
+
+    // Ranks incidents by tf-idf over their indexed documents, weighted by
+    // field, with a bonus for documents containing the query as a phrase
+    pub(crate) fn search(&self, query: &str, limit: u32) -> Result<Vec<SearchResult>, String> {
+        let mut seen = BTreeSet::new();
+        let terms: Vec<String> = tokenize(query).into_iter().filter(|t| seen.insert(t.clone())).collect();
+        if terms.is_empty() {
+            return Err(format!("Search query '{}' has no searchable terms", query));
+        }
+        let limit = match limit as usize {
+            0 => DEFAULT_SEARCH_LIMIT,
+            n => n.min(MAX_SEARCH_LIMIT),
+        };
+        let phrase = query.trim().to_lowercase();
+
+        let mut incidents: BTreeMap<String, Incident> = BTreeMap::new();
+        // term frequencies of each candidate document's current text
+        let mut documents: BTreeMap<DocRef, (String, HashMap<String, usize>)> = BTreeMap::new();
+        let mut document_frequency: BTreeMap<&str, usize> = BTreeMap::new();
+
+        for term in &terms {
+            let mut matching_incidents: BTreeSet<String> = BTreeSet::new();
+            for doc in self.postings(term) {
+                if !documents.contains_key(&doc) {
+                    if !incidents.contains_key(&doc.incident_id) {
+                        match self.incidents.get(&doc.incident_id) {
+                            Some(incident) => incidents.insert(doc.incident_id.clone(), incident),
+                            None => continue,
+                        };
+                    }
+                    let Some(text) = self.document_text(&doc, &incidents[&doc.incident_id]) else {
+                        continue;
+                    };
+                    let mut counts: HashMap<String, usize> = HashMap::new();
+                    for token in tokenize(&text) {
+                        *counts.entry(token).or_default() += 1;
+                    }
+                    documents.insert(doc.clone(), (text, counts));
+                }
+                if documents[&doc].1.contains_key(term) {
+                    matching_incidents.insert(doc.incident_id.clone());
+                }
+            }
+            document_frequency.insert(term, matching_incidents.len());
+        }
+
+        let total = self.incident_ids.len().max(1) as f64;
+        let idf = |term: &str| {
+            let df = document_frequency.get(term).copied().unwrap_or(0) as f64;
+            (1.0 + total / df.max(1.0)).ln()
+        };
+
+        let mut scored: BTreeMap<String, (f64, BTreeSet<String>, Vec<SearchHit>)> = BTreeMap::new();
+        for (doc, (text, counts)) in &documents {
+            let matched: Vec<String> = terms.iter().filter(|t| counts.contains_key(*t)).cloned().collect();
+            if matched.is_empty() {
+                continue;
+            }
+
+            let mut score: f64 = matched
+                .iter()
+                .map(|term| (1.0 + (counts[term] as f64).ln()) * idf(term))
+                .sum();
+            if terms.len() > 1 && text.to_lowercase().contains(&phrase) {
+                score *= 2.0;
+            }
+            score *= doc.field.weight();
+
+            let entry = scored.entry(doc.incident_id.clone()).or_default();
+            entry.0 += score;
+            entry.1.extend(matched.iter().cloned());
+            entry.2.push(SearchHit {
+                field: doc.field,
+                key: doc.key.clone(),
+                snippet: snippet(text, &phrase, &matched),
+            });
+        }
+
+        let mut results: Vec<SearchResult> = scored
+            .into_iter()
+            .map(|(incident_id, (score, matched_terms, mut hits))| {
+                let incident = &incidents[&incident_id];
+                hits.sort_by(|a, b| b.field.weight().total_cmp(&a.field.weight()).then_with(|| a.key.cmp(&b.key)));
+                SearchResult {
+                    score: (score * 1000.0).round() / 1000.0,
+                    severity: incident.severity,
+                    status: incident.status,
+                    service: incident.service.clone(),
+                    created_at: incident.created_at.clone(),
+                    matched_terms: matched_terms.into_iter().collect(),
+                    hits,
+                    incident_id,
+                }
+            })
+            .collect();
+
+        // Best match first; newer incidents win ties
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.created_at.cmp(&a.created_at))
+                .then_with(|| a.incident_id.cmp(&b.incident_id))
+        });
+        results.truncate(limit);
+        Ok(results)
+    }
+}
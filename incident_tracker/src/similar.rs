@@ -0,0 +1,109 @@
+use crate::search::{tokenize, Field};
+use crate::{Incident, IncidentTrackerContractState};
+use incident_common::{IncidentStatus, Severity};
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+pub const MAX_SIMILAR_INCIDENTS: usize = 5;
+// below this score a match is more noise than help
+const MIN_SIMILARITY: f64 = 0.2;
+
+const DESCRIPTION_WEIGHT: f64 = 0.6;
+const SERVICE_WEIGHT: f64 = 0.25;
+const SEVERITY_WEIGHT: f64 = 0.15;
+
+#[derive(Debug, Serialize)]
+pub struct SimilarIncident {
+    pub incident_id: String,
+    pub similarity: f64,
+    pub description: String,
+    pub service: String,
+    pub severity: Severity,
+    pub status: IncidentStatus,
+    pub created_at: String,
+    pub resolved_at: Option<String>,
+    pub resolution_notes: String,
+    pub shared_terms: Vec<String>,
+}
+
+// 1.0 for the same severity, falling off linearly to 0.0 for P0 against P3
+fn severity_closeness(a: Severity, b: Severity) -> f64 {
+    let distance = (a as i32 - b as i32).abs() as f64;
+    let max = (Severity::ALL.len() - 1) as f64;
+    1.0 - distance / max
+}
+
+fn resolved_at(incident: &Incident) -> Option<String> {
+    incident
+        .status_history
+        .iter()
+        .rev()
+        .find(|t| t.to == IncidentStatus::Resolved)
+        .map(|t| t.timestamp.clone())
+}
+
+// Scores a past incident against a new one: Jaccard overlap of description
+// terms, plus matching service and nearby severity
+fn score(incident: &Incident, terms: &BTreeSet<String>, past: &Incident) -> Option<SimilarIncident> {
+    // postings are never pruned, so overlap is measured on the current text
+    let past_terms: BTreeSet<String> = tokenize(&past.description).into_iter().collect();
+    let shared: Vec<String> = terms.intersection(&past_terms).cloned().collect();
+    if shared.is_empty() {
+        return None;
+    }
+    let union = terms.union(&past_terms).count();
+    let overlap = shared.len() as f64 / union as f64;
+    let same_service = if past.service.eq_ignore_ascii_case(&incident.service) { 1.0 } else { 0.0 };
+
+    let similarity = DESCRIPTION_WEIGHT * overlap
+        + SERVICE_WEIGHT * same_service
+        + SEVERITY_WEIGHT * severity_closeness(incident.severity, past.severity);
+    if similarity < MIN_SIMILARITY {
+        return None;
+    }
+
+    Some(SimilarIncident {
+        incident_id: past.id.clone(),
+        similarity: (similarity * 1000.0).round() / 1000.0,
+        description: past.description.clone(),
+        service: past.service.clone(),
+        severity: past.severity,
+        status: past.status,
+        created_at: past.created_at.clone(),
+        resolved_at: resolved_at(past),
+        resolution_notes: past.notes.clone(),
+        shared_terms: shared,
+    })
+}
+
+impl IncidentTrackerContractState {
+    // Past incidents resembling a new one. Candidates come from the search
+    // index postings of the new description's terms, so only incidents sharing
+    // at least one term are loaded and scored. Resolved incidents win ties,
+    // since their notes say what fixed the problem.
+    pub(crate) fn similar_incidents(&self, incident: &Incident, limit: usize) -> Vec<SimilarIncident> {
+        let terms: BTreeSet<String> = tokenize(&incident.description).into_iter().collect();
+        let candidates: BTreeSet<String> = terms
+            .iter()
+            .flat_map(|term| self.postings(term))
+            .filter(|doc| doc.field == Field::Description && doc.incident_id != incident.id)
+            .map(|doc| doc.incident_id)
+            .collect();
+
+        let mut matches: Vec<SimilarIncident> = candidates
+            .iter()
+            .filter_map(|id| self.incidents.get(id))
+            .filter(|past| past.merged_into.is_none())
+            .filter_map(|past| score(incident, &terms, &past))
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.similarity
+                .total_cmp(&a.similarity)
+                .then_with(|| b.resolved_at.is_some().cmp(&a.resolved_at.is_some()))
+                .then_with(|| b.created_at.cmp(&a.created_at))
+        });
+        matches.truncate(limit);
+        matches
+    }
+}
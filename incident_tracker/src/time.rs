@@ -0,0 +1,173 @@
+// Minimal RFC 3339 handling; the contract has no access to chrono.
+
+fn parse_number(value: &str, field: &str, timestamp: &str) -> Result<i64, String> {
+    if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid timestamp '{}': bad {}", timestamp, field));
+    }
+    value
+        .parse()
+        .map_err(|_| format!("Invalid timestamp '{}': bad {}", timestamp, field))
+}
+
+// Days since 1970-01-01 for a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+// Inverse of days_from_civil
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = if month <= 2 { yoe + era * 400 + 1 } else { yoe + era * 400 };
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Parses an RFC 3339 timestamp such as 2026-01-05T10:00:00Z or
+// 2026-01-05T15:30:00.250+05:30 into Unix seconds (fractions are dropped).
+pub fn parse_timestamp(timestamp: &str) -> Result<i64, String> {
+    let ts = timestamp.trim();
+    let bytes = ts.as_bytes();
+    if !ts.is_ascii() || bytes.len() < 20 || bytes[4] != b'-' || bytes[7] != b'-' || !matches!(bytes[10], b'T' | b't' | b' ')
+        || bytes[13] != b':' || bytes[16] != b':'
+    {
+        return Err(format!("Invalid timestamp '{}': expected RFC 3339, e.g. 2026-01-05T10:00:00Z", timestamp));
+    }
+
+    let year = parse_number(&ts[0..4], "year", timestamp)?;
+    let month = parse_number(&ts[5..7], "month", timestamp)?;
+    let day = parse_number(&ts[8..10], "day", timestamp)?;
+    let hour = parse_number(&ts[11..13], "hour", timestamp)?;
+    let minute = parse_number(&ts[14..16], "minute", timestamp)?;
+    let second = parse_number(&ts[17..19], "second", timestamp)?;
+
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month)
+        || hour > 23 || minute > 59 || second > 60
+    {
+        return Err(format!("Invalid timestamp '{}': date or time out of range", timestamp));
+    }
+
+    // Skip fractional seconds, then read the zone designator
+    let mut rest = &ts[19..];
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let digits = fraction.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return Err(format!("Invalid timestamp '{}': bad fractional seconds", timestamp));
+        }
+        rest = &fraction[digits..];
+    }
+
+    let offset = match rest {
+        "Z" | "z" => 0,
+        _ if rest.len() == 6 && matches!(&rest[0..1], "+" | "-") && &rest[3..4] == ":" => {
+            let hours = parse_number(&rest[1..3], "offset", timestamp)?;
+            let minutes = parse_number(&rest[4..6], "offset", timestamp)?;
+            if hours > 23 || minutes > 59 {
+                return Err(format!("Invalid timestamp '{}': offset out of range", timestamp));
+            }
+            let offset = hours * 3600 + minutes * 60;
+            if rest.starts_with('-') { -offset } else { offset }
+        }
+        _ => return Err(format!("Invalid timestamp '{}': missing or bad UTC offset", timestamp)),
+    };
+
+    let days = days_from_civil(year, month, day);
+    Ok(days * 86400 + hour * 3600 + minute * 60 + second.min(59) - offset)
+}
+
+// Formats Unix seconds as UTC RFC 3339, e.g. 2026-01-05T10:00:00Z
+pub fn format_timestamp(seconds: i64) -> String {
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86400));
+    let secs = seconds.rem_euclid(86400);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
+// Rewrites any RFC 3339 timestamp in UTC at whole-second precision, so that
+// stored timestamps sort correctly as plain strings
+pub fn normalize_timestamp(timestamp: &str) -> Result<String, String> {
+    parse_timestamp(timestamp).map(format_timestamp)
+}
+
+// Block times outside this range mean the runtime returned something other
+// than a wall-clock time, e.g. a counter or a value in the wrong unit
+const MIN_BLOCK_TIME: i64 = 946_684_800; // 2000-01-01T00:00:00Z
+const MAX_BLOCK_TIME: i64 = 4_102_444_800; // 2100-01-01T00:00:00Z
+
+// Runtime::block_timestamp returns the block time as an RFC 3339 string.
+// Bare numbers are rejected rather than guessed at, since seconds and
+// milliseconds cannot be told apart reliably.
+pub fn parse_block_time(block_time: &str) -> Result<i64, String> {
+    let seconds = parse_timestamp(block_time).map_err(|e| format!("Block time unavailable: {}", e))?;
+    if !(MIN_BLOCK_TIME..MAX_BLOCK_TIME).contains(&seconds) {
+        return Err(format!("Block time unavailable: '{}' is outside 2000-2100", block_time.trim()));
+    }
+    Ok(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_are_converted_to_utc() {
+        let result = normalize_timestamp("2026-01-05T15:30:00+05:30");
+        assert_eq!(result, Ok("2026-01-05T10:00:00Z".to_string()));
+        let result = normalize_timestamp("2026-01-04T23:30:00-02:00");
+        assert_eq!(result, Ok("2026-01-05T01:30:00Z".to_string()));
+    }
+
+    #[test]
+    fn fractional_seconds_are_dropped() {
+        let result = normalize_timestamp("2026-01-05T10:00:00.999Z");
+        assert_eq!(result, Ok("2026-01-05T10:00:00Z".to_string()));
+        assert!(parse_timestamp("2026-01-05T10:00:00.Z").is_err());
+    }
+
+    #[test]
+    fn leap_days_follow_the_gregorian_rules() {
+        let result = normalize_timestamp("2024-02-29T12:00:00Z");
+        assert_eq!(result, Ok("2024-02-29T12:00:00Z".to_string()));
+        assert!(parse_timestamp("2025-02-29T12:00:00Z").is_err());
+        assert!(parse_timestamp("2100-02-29T12:00:00Z").is_err());
+        assert!(parse_timestamp("2000-02-29T12:00:00Z").is_ok());
+    }
+
+    #[test]
+    fn block_time_must_be_rfc3339_in_a_plausible_range() {
+        let result = parse_block_time("2026-01-05T10:00:00Z");
+        assert_eq!(result, Ok(1_767_607_200));
+        // Unix seconds and milliseconds alike are rejected, not guessed at
+        assert!(parse_block_time("1767607200").is_err());
+        assert!(parse_block_time("1767607200000").is_err());
+        assert!(parse_block_time("1970-01-01T00:00:00Z").is_err());
+        assert!(parse_block_time("").is_err());
+    }
+}
@@ -0,0 +1,194 @@
+use crate::metrics::durations;
+use crate::time::parse_timestamp;
+use crate::Incident;
+use incident_common::Severity;
+
+pub const DAY: i64 = 86_400;
+pub const WEEK: i64 = 7 * DAY;
+// Longest window a plot may cover, so a query never walks an unbounded range
+pub const MAX_WINDOW_DAYS: i64 = 366;
+
+// (Unix seconds at the start of the bucket, value)
+pub type Series = Vec<(i64, f64)>;
+
+fn start_of_day(t: i64) -> i64 {
+    t.div_euclid(DAY) * DAY
+}
+
+// Weeks start on Monday; the Unix epoch fell on a Thursday
+fn start_of_week(t: i64) -> i64 {
+    (t - 4 * DAY).div_euclid(WEEK) * WEEK + 4 * DAY
+}
+
+// The timestamps the plots need, parsed once per incident. Incidents with
+// an unparseable report time are left out of every plot. A merge counts as
+// closing the duplicate: it stops being open and is not counted as a report.
+pub struct Sample {
+    severity: Severity,
+    merged: bool,
+    created: i64,
+    updated: i64,
+    // (time, open afterwards) in time order, starting with the report itself
+    changes: Vec<(i64, bool)>,
+    // (resolved at, time to resolve) for incidents that stayed resolved
+    resolution: Option<(i64, i64)>,
+}
+
+pub fn samples(incidents: &[Incident]) -> Vec<Sample> {
+    incidents
+        .iter()
+        .filter_map(|incident| {
+            let created = parse_timestamp(&incident.created_at).ok()?;
+            let mut changes: Vec<(i64, bool)> = incident
+                .status_history
+                .iter()
+                .filter_map(|t| parse_timestamp(&t.timestamp).ok().map(|at| (at, t.to.is_active())))
+                .collect();
+            changes.insert(0, (created, true));
+            // stable, so transitions sharing a timestamp keep their write order
+            changes.sort_by_key(|(at, _)| *at);
+            // a merged incident takes no changes afterwards, so updated_at is the merge
+            let updated = parse_timestamp(&incident.updated_at).unwrap_or(created);
+            let merged = incident.merged_into.is_some();
+            if merged {
+                changes.push((updated, false));
+            }
+            let resolution = durations(incident)
+                .ok()
+                .and_then(|d| d.resolve)
+                .filter(|_| !merged)
+                .map(|resolve| (created + resolve, resolve));
+
+            Some(Sample {
+                severity: incident.severity,
+                merged,
+                created,
+                updated,
+                changes,
+                resolution,
+            })
+        })
+        .collect()
+}
+
+// Day-aligned [start, end) covering the requested window. An open end
+// defaults to the last recorded change; an open start to the first report,
+// or MAX_WINDOW_DAYS before the end if that is later.
+pub fn window(samples: &[Sample], from: &str, to: &str) -> Result<Option<(i64, i64)>, String> {
+    let end = if to.is_empty() {
+        samples.iter().map(|s| s.updated.max(s.created)).max().map(|t| start_of_day(t) + DAY)
+    } else {
+        Some(start_of_day(parse_timestamp(to)? - 1) + DAY)
+    };
+    let Some(end) = end else {
+        return Ok(None);
+    };
+
+    let start = if from.is_empty() {
+        match samples.iter().map(|s| s.created).min() {
+            Some(first) => start_of_day(first).max(end - MAX_WINDOW_DAYS * DAY),
+            None => return Ok(None),
+        }
+    } else {
+        start_of_day(parse_timestamp(from)?)
+    };
+
+    if start >= end {
+        return Ok(None);
+    }
+    let days = (end - start) / DAY;
+    if days > MAX_WINDOW_DAYS {
+        return Err(format!("Window covers {} days; at most {} are allowed", days, MAX_WINDOW_DAYS));
+    }
+    Ok(Some((start, end)))
+}
+
+// Index of the first bucket whose end-of-day instant is at or after t
+fn first_bucket_ending_after(t: i64, start: i64, buckets: usize) -> usize {
+    let offset = t - (start + DAY - 1);
+    let index = -(-offset).div_euclid(DAY);
+    index.clamp(0, buckets as i64) as usize
+}
+
+// Number of incidents reported per day, one series per severity
+pub fn incidents_per_day(samples: &[Sample], start: i64, end: i64) -> Vec<(Severity, Series)> {
+    let buckets = ((end - start) / DAY) as usize;
+    let mut counts = vec![vec![0u64; buckets]; Severity::ALL.len()];
+    for sample in samples.iter().filter(|s| !s.merged && (start..end).contains(&s.created)) {
+        counts[sample.severity as usize][((sample.created - start) / DAY) as usize] += 1;
+    }
+
+    Severity::ALL
+        .into_iter()
+        .zip(counts)
+        .map(|(severity, counts)| {
+            let series = counts
+                .into_iter()
+                .enumerate()
+                .map(|(i, count)| (start + i as i64 * DAY, count as f64))
+                .collect();
+            (severity, series)
+        })
+        .collect()
+}
+
+// Number of open incidents at the end of each day. Each open stretch of an
+// incident adds one to the days it spans, via a difference array.
+pub fn open_incidents(samples: &[Sample], start: i64, end: i64) -> Series {
+    let buckets = ((end - start) / DAY) as usize;
+    let mut delta = vec![0i64; buckets + 1];
+    for sample in samples {
+        let mut opened: Option<i64> = None;
+        for &(at, open) in &sample.changes {
+            match (opened, open) {
+                (None, true) => opened = Some(at),
+                (Some(since), false) => {
+                    let (from, to) = (
+                        first_bucket_ending_after(since, start, buckets),
+                        first_bucket_ending_after(at, start, buckets),
+                    );
+                    if from < to {
+                        delta[from] += 1;
+                        delta[to] -= 1;
+                    }
+                    opened = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(since) = opened {
+            delta[first_bucket_ending_after(since, start, buckets)] += 1;
+        }
+    }
+
+    let mut open = 0;
+    (0..buckets)
+        .map(|i| {
+            open += delta[i];
+            (start + i as i64 * DAY, open as f64)
+        })
+        .collect()
+}
+
+// Mean time to resolve in minutes, bucketed by the week of resolution.
+// Weeks without resolutions are left out rather than plotted as zero.
+pub fn mttr_per_week(samples: &[Sample], start: i64, end: i64) -> Series {
+    let first = start_of_week(start);
+    let weeks = ((end - first + WEEK - 1) / WEEK) as usize;
+    let mut totals = vec![(0i64, 0u64); weeks];
+    for (resolved_at, duration) in samples.iter().filter_map(|s| s.resolution) {
+        let week = start_of_week(resolved_at);
+        if week >= first && week < end {
+            let total = &mut totals[((week - first) / WEEK) as usize];
+            total.0 += duration;
+            total.1 += 1;
+        }
+    }
+
+    totals
+        .into_iter()
+        .enumerate()
+        .filter(|(_, (_, count))| *count > 0)
+        .map(|(i, (sum, count))| (first + i as i64 * WEEK, sum as f64 / count as f64 / 60.0))
+        .collect()
+}
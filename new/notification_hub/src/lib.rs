@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use weil_macros::{constructor, query, smart_contract, WeilType};
 use serde_json::json;
+use incident_common::Severity;
 use weil_rs::config::Secrets;
 
 
@@ -44,14 +45,9 @@ impl NotificationHub for NotificationHubContractState {
     async fn send_slack(&self, message: String, severity: String) -> Result<String, String> {
         let config = self.secrets.config();
         
-        // Color code by severity
-        let color = match severity.as_str() {
-            "P0" => "#FF0000", // Red
-            "P1" => "#FFA500", // Orange
-            "P2" => "#FFFF00", // Yellow
-            "P3" => "#00FF00", // Green
-            _ => "#808080"     // Gray
-        };
+        // Color code by severity, rejecting unknown levels
+        let severity: Severity = severity.parse()?;
+        let color = severity.hex_color();
         
         // In production, this would make actual HTTP call to Slack webhook
         // For demo/WASM compatibility, return success response
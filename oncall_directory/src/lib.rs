@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use weil_macros::{constructor, mutate, query, secured, smart_contract, WeilType};
 use weil_rs::config::Secrets;
 use serde_json::json;
+use incident_common::Severity;
 
 
 #[derive(Debug, Serialize, Deserialize, WeilType, Default)]
@@ -80,26 +81,27 @@ where
 
     #[query]
     async fn get_escalation_chain(&self, team: String, severity: String) -> Result<String, String> {
-        let chain = match severity.as_str() {
-            "P0" => json!({
+        let severity: Severity = severity.parse()?;
+        let chain = match severity {
+            Severity::P0 => json!({
                 "levels": [
                     {"role": "on_call_engineer", "contact_immediately": true},
                     {"role": "team_lead", "contact_after": "5min"},
                     {"role": "vp_engineering", "contact_after": "15min"}
                 ]
             }),
-            "P1" => json!({
+            Severity::P1 => json!({
                 "levels": [
                     {"role": "on_call_engineer", "contact_immediately": true},
                     {"role": "team_lead", "contact_after": "30min"}
                 ]
             }),
-            "P2" => json!({
+            Severity::P2 => json!({
                 "levels": [
                     {"role": "on_call_engineer", "contact_immediately": false}
                 ]
             }),
-            _ => json!({
+            Severity::P3 => json!({
                 "levels": [
                     {"role": "ticket_queue", "contact_immediately": false}
                 ]